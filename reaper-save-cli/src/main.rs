@@ -1,7 +1,10 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use eyre::{Result, WrapErr};
-use reaper_save_rs::prelude::ReaperProject;
-use std::path::PathBuf;
+use reaper_save_rs::{
+    low_level::{Attribute, Object},
+    prelude::{ReaperProject, SerializeAndDeserialize},
+};
+use std::{path::PathBuf, process::ExitCode};
 #[allow(unused_imports)]
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -14,32 +17,232 @@ struct Cli {
     command: Command,
 }
 
+/// how `validate` reports its result
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum Format {
+    /// log the result, listing the tracks
+    #[default]
+    Text,
+    /// print a json object per file on stdout
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Command {
-    /// validate the file to check if parses properly
+    /// validate the file to check if parses properly, exiting with a failure if it does not
     Validate {
         /// file to validate
+        #[arg(short, long, required_unless_present = "recursive")]
+        file_path: Option<PathBuf>,
+        /// validate every `*.rpp` file in this directory and its subdirectories instead
+        #[arg(short, long, conflicts_with = "file_path")]
+        recursive: Option<PathBuf>,
+        /// output format
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// do not list the tracks
         #[arg(short, long)]
+        quiet: bool,
+    },
+    /// print the value at `path`, e.g. `TRACK[2]/VOLPAN[1]`
+    Get {
+        /// file to read
         file_path: PathBuf,
+        /// slash separated `NAME[index]` steps, the last one naming a line
+        path: String,
     },
+    /// replace the value at `path`, rewriting the file in place
+    Set {
+        /// file to modify
+        file_path: PathBuf,
+        /// slash separated `NAME[index]` steps, the last one naming a line
+        path: String,
+        /// new value as it would appear in the file, e.g. `"a name"` or `0.5`
+        value: String,
+        /// write the result here instead of overwriting `file_path`
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn read_project(file_path: &PathBuf) -> Result<ReaperProject> {
+    std::fs::read_to_string(file_path)
+        .wrap_err("reading file from disk")
+        .and_then(|text| ReaperProject::parse_from_str(&text).wrap_err("parsing file"))
+        .wrap_err_with(|| format!("reading [{}]", file_path.display()))
+}
+
+/// result of validating a single file
+struct Validation {
+    file_path: PathBuf,
+    /// names of the tracks (`None` for unnamed ones), if the file parsed
+    track_names: Result<Vec<Option<String>>>,
+}
+
+impl Validation {
+    fn new(file_path: PathBuf) -> Self {
+        let track_names = read_project(&file_path).map(|project| {
+            project
+                .track_refs()
+                .map(|track| track.name_opt())
+                .collect::<Vec<_>>()
+        });
+        Self {
+            file_path,
+            track_names,
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        self.track_names.is_ok()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "file": self.file_path.display().to_string(),
+            "ok": self.is_ok(),
+            "track_count": self.track_names.as_ref().ok().map(Vec::len),
+            "error": self.track_names.as_ref().err().map(|error| format!("{error:#}")),
+        })
+    }
+
+    fn report(&self, format: Format, quiet: bool) {
+        let file_path = &self.file_path;
+        match (format, &self.track_names) {
+            (Format::Json, _) => println!("{}", self.to_json()),
+            (Format::Text, Ok(track_names)) => {
+                info!(?file_path, track_count=%track_names.len(), "OK");
+                if !quiet {
+                    for (idx, name) in track_names.iter().enumerate() {
+                        info!("{}. {}", idx + 1, name.as_deref().unwrap_or("(unnamed)"));
+                    }
+                }
+            }
+            (Format::Text, Err(error)) => error!(?file_path, "{error:#}"),
+        }
+    }
+}
+
+/// every `*.rpp` file under `dir`, in a stable order. symlinked directories are not followed,
+/// so a link back up the tree cannot send the walk in circles
+fn project_files(dir: &PathBuf) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir).wrap_err_with(|| format!("listing [{}]", dir.display()))? {
+        let entry = entry.wrap_err("reading directory entry")?;
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .wrap_err_with(|| format!("reading file type of [{}]", path.display()))?;
+        if file_type.is_dir() {
+            files.extend(project_files(&path)?);
+        } else if file_type.is_symlink() && path.is_dir() {
+            debug!(?path, "skipping symlinked directory");
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("rpp"))
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
 }
 
-fn main() -> Result<()> {
-    tracing_subscriber::fmt().init();
+fn main() -> Result<ExitCode> {
+    // stdout is reserved for command output
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
     color_eyre::install().ok();
     let Cli { command } = Cli::parse();
     match command {
-        Command::Validate { file_path } => std::fs::read_to_string(&file_path)
-            .wrap_err("reading file from disk")
-            .and_then(|text| ReaperProject::parse_from_str(&text).wrap_err("parsing file"))
-            .wrap_err_with(|| format!("validating [{}]", file_path.display()))
-            .and_then(|project| -> Result<()> {
-                let tracks = project.tracks();
-                info!(?file_path, track_count=%tracks.len(), "OK");
-                for (idx, track) in tracks.iter().enumerate() {
-                    info!("{}. {}", idx + 1, track.name()?);
-                }
-                Ok(())
-            }),
+        Command::Validate {
+            file_path,
+            recursive,
+            format,
+            quiet,
+        } => {
+            let is_recursive = recursive.is_some();
+            let files = match (file_path, recursive) {
+                (_, Some(dir)) => project_files(&dir)?,
+                (Some(file_path), None) => vec![file_path],
+                (None, None) => eyre::bail!("either a file or a directory is required"),
+            };
+            let total = files.len();
+            let failed = files
+                .into_iter()
+                .map(Validation::new)
+                .inspect(|validation| validation.report(format, quiet))
+                .filter(|validation| !validation.is_ok())
+                .count();
+            if is_recursive {
+                info!("{} ok, {failed} failed", total - failed);
+            }
+            Ok(match failed {
+                0 => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            })
+        }
+        Command::Get { file_path, path } => read_project(&file_path)
+            .and_then(|project| {
+                AsRef::<Object>::as_ref(&project)
+                    .attribute_at(&path)
+                    .and_then(|attribute| attribute.serialize_inline())
+                    .wrap_err_with(|| format!("getting [{path}]"))
+                    .map(|value| println!("{value}"))
+            })
+            .map(|()| ExitCode::SUCCESS),
+        Command::Set {
+            file_path,
+            path,
+            value,
+            output,
+        } => read_project(&file_path)
+            .and_then(|mut project| {
+                let value = value
+                    .parse::<Attribute>()
+                    .wrap_err_with(|| format!("parsing value [{value}]"))?;
+                *AsMut::<Object>::as_mut(&mut project)
+                    .attribute_at_mut(&path)
+                    .wrap_err_with(|| format!("setting [{path}]"))? = value;
+                let output = output.unwrap_or(file_path);
+                project
+                    .serialize_to_string()
+                    .wrap_err("serializing project")
+                    .and_then(|text| std::fs::write(&output, text).wrap_err("writing file to disk"))
+                    .wrap_err_with(|| format!("writing [{}]", output.display()))
+            })
+            .map(|()| ExitCode::SUCCESS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_project_files_skips_symlink_cycles() -> Result<()> {
+        let root = std::env::temp_dir().join(format!("reaper-save-cli-{}", std::process::id()));
+        let nested = root.join("nested");
+        // left over from an earlier run that failed
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&nested)?;
+        std::fs::write(root.join("a.rpp"), "")?;
+        std::fs::write(nested.join("b.RPP"), "")?;
+        std::fs::write(nested.join("notes.txt"), "")?;
+        std::os::unix::fs::symlink("..", nested.join("up"))?;
+        std::os::unix::fs::symlink(root.join("a.rpp"), nested.join("linked.rpp"))?;
+        let files = project_files(&root);
+        std::fs::remove_dir_all(&root)?;
+        assert_eq!(
+            files?,
+            [
+                root.join("a.rpp"),
+                nested.join("b.RPP"),
+                nested.join("linked.rpp")
+            ]
+        );
+        Ok(())
     }
 }
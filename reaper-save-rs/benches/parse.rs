@@ -0,0 +1,58 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use reaper_save_rs::low_level::{self, SerializeAndDeserialize};
+
+const EXAMPLE_1: &str = include_str!("../test_data/barbarah-anne.rpp");
+const WEIRD_TRACK_2: &str = include_str!("../test_data/weird-track-2.rpp");
+
+/// the example project with 40 copies of a track carrying a large plugin state chunk,
+/// which is roughly what a real mixing session looks like
+fn large_project() -> String {
+    let track = WEIRD_TRACK_2
+        .lines()
+        .map(|line| format!("  {line}\r\n"))
+        .collect::<String>();
+    let closing = EXAMPLE_1.trim_end().len() - 1;
+    let mut project = EXAMPLE_1[..closing].to_owned();
+    (0..40).for_each(|_| project.push_str(&track));
+    project.push_str(&EXAMPLE_1[closing..]);
+    project
+}
+
+fn parse(c: &mut Criterion) {
+    let large_project = large_project();
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("barbarah-anne", |b| {
+        b.iter(|| low_level::from_str(black_box(EXAMPLE_1)).unwrap())
+    });
+    group.bench_function("weird-track-2", |b| {
+        b.iter(|| low_level::from_str(black_box(WEIRD_TRACK_2)).unwrap())
+    });
+    group
+        .sample_size(20)
+        .bench_function("40 tracks with plugin state", |b| {
+            b.iter(|| low_level::from_str(black_box(&large_project)).unwrap())
+        });
+    group.finish();
+}
+
+/// a track receiving from 5000 others, so nearly all of it is long `AUXRECV` lines
+fn long_lines() -> String {
+    let receives = (0..5000)
+        .map(|source| format!("  AUXRECV {source} 0 1 0 0 0 0 0 0 -1:U 0 -1 ''\r\n"))
+        .collect::<String>();
+    format!("<TRACK\r\n  NAME \"bus\"\r\n{receives}>")
+}
+
+fn serialize(c: &mut Criterion) {
+    let large_project = low_level::from_str(&large_project()).unwrap();
+    let long_lines = low_level::from_str(&long_lines()).unwrap();
+    c.bench_function("serialize 40 tracks with plugin state", |b| {
+        b.iter(|| black_box(&large_project).serialize_inline().unwrap())
+    });
+    c.bench_function("serialize 5000 long lines", |b| {
+        b.iter(|| black_box(&long_lines).serialize_inline().unwrap())
+    });
+}
+
+criterion_group!(benches, parse, serialize);
+criterion_main!(benches);
@@ -1,24 +1,157 @@
 use crate::low_level::{
-    self, AttributeKind, AttributeName, Entry, Line, Object, SerializeAndDeserialize,
+    self, Attribute, AttributeKind, AttributeName, Entry, Int, Line, LineEnding, Object,
+    ParseOptions, ReaperString, ReaperUid, SerializeAndDeserialize, SerializeOptions,
 };
 use derive_more::{AsMut, AsRef};
+use low_level::lenient::ParseWarning;
+use ordered_float::OrderedFloat;
+use ref_cast::{ref_cast_custom, RefCastCustom};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 use tap::prelude::*;
 
+pub mod envelope;
 pub mod error;
+pub mod folders;
+pub mod groups;
+pub mod notes;
+pub mod repair;
+pub mod routing;
+pub mod takes;
 use error::Result;
 
 fn assert_attribute_name(object: Object, attribute_name: &str) -> Result<Object> {
-    matches_attribute_name_ref(&object, attribute_name)
-        .then(|| object.clone())
-        .ok_or_else(|| error::Error::InvalidObject {
+    match matches_attribute_name_ref(&object, attribute_name) {
+        true => Ok(object),
+        false => Err(error::Error::InvalidObject {
             expected: AttributeName::new(attribute_name.to_owned()),
-            got: object.header.attribute.clone(),
-        })
+            got: object.header.attribute,
+        }),
+    }
 }
 fn matches_attribute_name_ref(object: &Object, attribute_name: &str) -> bool {
     object.header.attribute.as_ref().eq(attribute_name)
 }
 
+/// `FILE` of every `<SOURCE>` among `entries`, descending into sources wrapping other
+/// sources (`SECTION`, `REVERSE`...) which keep the file in an inner `<SOURCE WAVE>`
+fn source_files(entries: &[Entry]) -> Vec<String> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.as_object())
+        .filter(|object| SourceWave::matches_object(object))
+        .flat_map(|object| {
+            SourceWave::from_object_raw(object.clone())
+                .file()
+                .and_then(|file| file.ok())
+                .map(str::to_owned)
+                .into_iter()
+                .chain(source_files(&object.values))
+        })
+        .collect()
+}
+
+fn as_number(field: &'static str, attribute: &Attribute) -> Result<f64> {
+    attribute
+        .as_f64()
+        .ok_or_else(|| error::Error::InvalidAttributeType {
+            field,
+            expected: AttributeKind::Float,
+            found: attribute.kind(),
+        })
+}
+
+fn as_int(field: &'static str, attribute: &Attribute) -> Result<i64> {
+    attribute
+        .as_i64()
+        .ok_or_else(|| error::Error::InvalidAttributeType {
+            field,
+            expected: AttributeKind::Int,
+            found: attribute.kind(),
+        })
+}
+
+/// `index`-th value of the `field` line, read as a number
+fn number_at(object: &Object, field: &'static str, index: usize) -> Option<Result<f64>> {
+    object
+        .attributes(field)
+        .and_then(|values| values.get(index))
+        .map(|attribute| as_number(field, attribute))
+}
+
+/// overwrites the `index`-th value of the `field` line, leaving the other values as they are
+fn set_attribute_at(
+    object: &mut Object,
+    field: &'static str,
+    index: usize,
+    value: Attribute,
+) -> Result<()> {
+    object
+        .attributes_mut(field)
+        .and_then(|values| values.get_mut(index))
+        .map(|attribute| *attribute = value)
+        .ok_or_else(|| error::Error::MissingAttribute {
+            attribute: AttributeName::new(field.to_owned()),
+        })
+}
+
+fn set_number_at(object: &mut Object, field: &'static str, index: usize, value: f64) -> Result<()> {
+    set_attribute_at(object, field, index, Attribute::Float(OrderedFloat(value)))
+}
+
+fn int_at(object: &Object, field: &'static str, index: usize) -> Option<Result<i64>> {
+    object
+        .attributes(field)
+        .and_then(|values| values.get(index))
+        .map(|attribute| as_int(field, attribute))
+}
+
+/// replaces the values of the `field` line, removing it for `None`. a missing line is added
+/// after the last line preceding the first child object
+fn set_line(object: &mut Object, field: &'static str, values: Option<Vec<Attribute>>) {
+    let position = object.values.iter().position(|entry| {
+        entry
+            .as_line()
+            .map(|line| line.attribute.as_ref() == field)
+            .unwrap_or(false)
+    });
+    match (position, values) {
+        (Some(position), None) => {
+            object.values.remove(position);
+        }
+        (Some(position), Some(values)) => {
+            if let Some(line) = object.values[position].as_line_mut() {
+                line.values = values;
+            }
+        }
+        (None, None) => {}
+        (None, Some(values)) => {
+            let position = object
+                .values
+                .iter()
+                .position(|entry| entry.as_object().is_some())
+                .unwrap_or(object.values.len());
+            object.values.insert(
+                position,
+                Entry::Line(Line {
+                    attribute: AttributeName::new(field.to_owned()),
+                    values,
+                }),
+            );
+        }
+    }
+}
+
+fn required<T>(field: &'static str, value: Option<Result<T>>) -> Result<T> {
+    value.unwrap_or_else(|| {
+        Err(error::Error::MissingAttribute {
+            attribute: AttributeName::new(field.to_owned()),
+        })
+    })
+}
+
 thread_local! {
     pub static DUMMY_OBJECT: Object = {
         Object {
@@ -38,25 +171,48 @@ pub trait ObjectWrapper: Sized {
     fn matches_object(inner: &Object) -> bool {
         matches_attribute_name_ref(inner, Self::ATTRIBUTE_NAME)
     }
+    /// see [Object::summary]
+    fn summary(&self) -> String
+    where
+        Self: AsRef<Object>,
+    {
+        self.as_ref().summary()
+    }
+    /// the wrapped object, for reading lines there is no typed accessor for
+    fn as_object(&self) -> &Object
+    where
+        Self: AsRef<Object>,
+    {
+        self.as_ref()
+    }
+    /// runs `f` on the wrapped object, for adding or changing lines there is no typed accessor
+    /// for. the header is left to `f` as well, renaming it makes the wrapper invalid
+    fn with_object_mut<T>(&mut self, f: impl FnOnce(&mut Object) -> T) -> T
+    where
+        Self: AsMut<Object>,
+    {
+        f(self.as_mut())
+    }
     fn with_as_object_mut<T, F: FnOnce(&mut Self) -> T>(
         inner: &mut Object,
         with_as_object_mut: F,
     ) -> error::Result<T> {
-        DUMMY_OBJECT.with(|dummy_object| {
-            std::mem::replace(inner, dummy_object.clone()).pipe(|original| match Self::from_object(
-                original.clone(),
-            ) {
-                Ok(mut valid) => with_as_object_mut(&mut valid)
-                    .tap(|_| {
-                        let _ = std::mem::replace(inner, valid.destroy());
-                    })
-                    .pipe(Ok),
-                Err(message) => {
-                    let _ = std::mem::replace(inner, original);
-                    Err(message)
-                }
+        // checked up front so that `inner` never has to be cloned to survive a failed conversion
+        if !Self::matches_object(inner) {
+            return Err(error::Error::InvalidObject {
+                expected: AttributeName::new(Self::ATTRIBUTE_NAME.to_owned()),
+                got: inner.header.attribute.clone(),
+            });
+        }
+        DUMMY_OBJECT
+            .with(|dummy_object| {
+                let mut valid =
+                    Self::from_object_raw(std::mem::replace(inner, dummy_object.clone()));
+                with_as_object_mut(&mut valid).tap(|_| {
+                    let _ = std::mem::replace(inner, valid.destroy());
+                })
             })
-        })
+            .pipe(Ok)
     }
 }
 
@@ -76,12 +232,80 @@ macro_rules! debug_impl {
 debug_impl!(ReaperProject);
 debug_impl!(Track);
 debug_impl!(Item);
+debug_impl!(FxChain);
+debug_impl!(RenderCfg);
+debug_impl!(Envelope);
+
+/// `object` the way it is written to a file, line ending after the last line included.
+/// objects which cannot be serialized fall back to their [`Debug`](std::fmt::Debug) form
+fn fmt_serialized(
+    object: &Object,
+    options: &SerializeOptions,
+    fallback: &impl std::fmt::Debug,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let mut out = String::new();
+    match object.serialize_with(&mut out, 0, options) {
+        Ok(_) => {
+            f.write_str(&out)?;
+            f.write_str(options.line_ending.as_str())
+        }
+        Err(_) => fallback.fmt(f),
+    }
+}
+
+impl std::fmt::Display for ReaperProject {
+    /// same text as [`ReaperProject::serialize_to_string`], but never touches the save time
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let options = SerializeOptions {
+            line_ending: self.line_ending,
+            ..Default::default()
+        };
+        fmt_serialized(&self.inner, &options, self, f)
+    }
+}
+
+impl std::str::FromStr for ReaperProject {
+    type Err = error::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::parse_from_str(input)
+    }
+}
+
+/// text form of wrappers for a single object, e.g. `<TRACK\r\n  NAME a\r\n>\r\n`
+macro_rules! fragment_impl {
+    ($ty:ty) => {
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fmt_serialized(&self.inner, &SerializeOptions::default(), self, f)
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = error::Error;
+
+            fn from_str(input: &str) -> Result<Self> {
+                low_level::parse_object(input)
+                    .map_err(Into::into)
+                    .and_then(Self::from_object)
+            }
+        }
+    };
+}
+
+fragment_impl!(Track);
+fragment_impl!(Item);
 
 impl ObjectWrapper for ReaperProject {
     const ATTRIBUTE_NAME: &'static str = "REAPER_PROJECT";
 
     fn from_object_raw(inner: Object) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            line_ending: LineEnding::default(),
+            touch_on_save: false,
+        }
     }
 
     fn destroy(self) -> Object {
@@ -91,62 +315,434 @@ impl ObjectWrapper for ReaperProject {
 
 #[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
 pub struct ReaperProject {
+    #[as_ref]
+    #[as_mut]
     inner: Object,
+    /// line ending of the parsed file, reproduced on serialization
+    line_ending: LineEnding,
+    /// refresh the save time in the header on serialization, like REAPER does
+    touch_on_save: bool,
 }
 
 impl ReaperProject {
     pub fn parse_from_str(input: &str) -> Result<Self> {
-        low_level::from_str(input)
+        Self::parse_from_str_with(input, &ParseOptions::default())
+    }
+    pub fn parse_from_str_with(input: &str, options: &ParseOptions) -> Result<Self> {
+        low_level::from_str_with(input, options)
             .map_err(Into::into)
             .and_then(Self::from_object)
+            .map(|project| project.with_line_ending(low_level::detect_line_ending(input)))
+    }
+    /// best-effort parse of a damaged project, see [`low_level::lenient`]. everything that
+    /// was fixed or dropped on the way is reported, files REAPER wrote come back without
+    /// warnings
+    pub fn parse_lenient(input: &str) -> (Option<Self>, Vec<ParseWarning>) {
+        let (object, mut warnings) = low_level::lenient::from_str_lenient(input);
+        let project = object.and_then(|object| match Self::from_object(object) {
+            Ok(project) => Some(project.with_line_ending(low_level::detect_line_ending(input))),
+            Err(error) => {
+                warnings.push(ParseWarning::Unparsable {
+                    reason: error.to_string(),
+                });
+                None
+            }
+        });
+        (project, warnings)
+    }
+    /// reads the whole stream and parses it, detecting the line ending like [`Self::parse_from_str`]
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(low_level::error::Error::from)?;
+        Self::parse_from_str(&input)
+    }
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+    pub fn with_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+    /// [`Self::touch`] before every serialization. off by default, so that an unmodified
+    /// project serializes to exactly what was parsed
+    pub fn with_touch_on_save(self, touch_on_save: bool) -> Self {
+        Self {
+            touch_on_save,
+            ..self
+        }
+    }
+    /// sets the save time in the header (see [`ProjectVersion::saved_at`]) to now
+    pub fn touch(&mut self) -> Result<()> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs() as i64)
+            .unwrap_or_default();
+        self.inner
+            .header
+            .values
+            .get_mut(2)
+            .map(|saved_at| *saved_at = Attribute::Int(Int(now)))
+            .ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(Self::ATTRIBUTE_NAME.to_owned()),
+            })
+    }
+    pub fn serialize_to_string(mut self) -> Result<String> {
+        if self.touch_on_save {
+            self.touch()?;
+        }
+        let options = SerializeOptions {
+            line_ending: self.line_ending,
+            ..Default::default()
+        };
+        low_level::to_string_with(self.inner, &options).map_err(Into::into)
+    }
+    /// same output as [`Self::serialize_to_string`], written straight into `writer`
+    pub fn serialize_to_writer(mut self, writer: impl std::io::Write) -> Result<()> {
+        if self.touch_on_save {
+            self.touch()?;
+        }
+        let options = SerializeOptions {
+            line_ending: self.line_ending,
+            ..Default::default()
+        };
+        low_level::to_writer_with(self.inner, writer, &options).map_err(Into::into)
+    }
+    /// the whole project tree as JSON, with every value annotated with its parsed kind
+    #[cfg(feature = "serde")]
+    pub fn to_typed_json(&self) -> serde_json::Value {
+        self.inner.to_typed_json()
+    }
+    /// format version, saving REAPER version and save time from the project header
+    pub fn version(&self) -> Result<ProjectVersion> {
+        const HEADER: &str = "REAPER_PROJECT";
+        let value = |index: usize| {
+            self.inner
+                .header
+                .values
+                .get(index)
+                .ok_or_else(|| error::Error::MissingAttribute {
+                    attribute: AttributeName::new(HEADER.to_owned()),
+                })
+        };
+        let app = value(1)?;
+        let app = app
+            .as_str()
+            .ok_or_else(|| error::Error::InvalidAttributeType {
+                field: HEADER,
+                expected: AttributeKind::String,
+                found: app.kind(),
+            })?;
+        let (reaper_version, platform) = match app.split_once('/') {
+            Some((version, platform)) => (version, Some(platform.to_owned())),
+            None => (app, None),
+        };
+        Ok(ProjectVersion {
+            format: as_number(HEADER, value(0)?)?,
+            reaper_version: reaper_version.to_owned(),
+            platform,
+            saved_at: as_int(HEADER, value(2)?)?,
+        })
+    }
+    pub fn render_cfg(&self) -> Option<RenderCfg> {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .find(|o| RenderCfg::matches_object(o))
+            .cloned()
+            .map(RenderCfg::from_object_raw)
+    }
+    /// replaces `<RENDER_CFG>`, adding it before the first object when the project has none
+    pub fn set_render_cfg(&mut self, render_cfg: RenderCfg) {
+        let values = &mut self.inner.values;
+        match values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .find(|o| RenderCfg::matches_object(o))
+        {
+            Some(existing) => *existing = render_cfg.destroy(),
+            None => {
+                let position = values
+                    .iter()
+                    .position(|entry| entry.as_object().is_some())
+                    .unwrap_or(values.len());
+                values.insert(position, Entry::Object(render_cfg.destroy()));
+            }
+        }
+    }
+    /// the master track is not a `<TRACK>` object but a set of `MASTER*` lines at the top level
+    /// of the project, with its effects in `<MASTERFXLIST>`
+    pub fn master_track(&self) -> Result<MasterTrack> {
+        Ok(MasterTrack {
+            volume: required("MASTER_VOLUME", number_at(&self.inner, "MASTER_VOLUME", 0))?,
+            pan: required("MASTER_VOLUME", number_at(&self.inner, "MASTER_VOLUME", 1))?,
+            channel_count: required("MASTER_NCH", number_at(&self.inner, "MASTER_NCH", 0))?
+                as usize,
+            fx_chain: self
+                .inner
+                .values
+                .iter()
+                .filter_map(|e| e.as_object())
+                .find(|o| matches_attribute_name_ref(o, FxChain::MASTER_ATTRIBUTE_NAME))
+                .cloned()
+                .map(FxChain::from_object_raw),
+        })
+    }
+    /// sets the master volume, as a linear gain (`1` is 0dB)
+    pub fn set_master_volume(&mut self, volume: f64) -> Result<()> {
+        set_number_at(&mut self.inner, "MASTER_VOLUME", 0, volume)
     }
-    pub fn serialize_to_string(self) -> Result<String> {
-        low_level::to_string(self.inner).map_err(Into::into)
+    /// what the parser made of the project, see [`low_level::stats`]. counted from the tree,
+    /// so edits since parsing show up too
+    pub fn parse_stats(&self) -> low_level::stats::ParseStats {
+        self.inner.parse_stats()
+    }
+    /// objects matching a css-like `selector`, see [`Object::query`]
+    pub fn query(&self, selector: &str) -> Vec<&Object> {
+        self.inner.query(selector)
+    }
+    pub fn query_one(&self, selector: &str) -> Option<&Object> {
+        self.inner.query_one(selector)
+    }
+    /// raw entries of the extension state `section`, the way `SetProjExtState` (under
+    /// `<EXTSTATE>`) or extensions like SWS (under `<EXTENSIONS>`) wrote them
+    pub fn extension_state(&self, section: &str) -> Option<&[Entry]> {
+        fn child<'a>(object: &'a Object, name: &str) -> Option<&'a Object> {
+            object
+                .values
+                .iter()
+                .filter_map(|e| e.as_object())
+                .find(|o| o.header.attribute.as_ref() == name)
+        }
+        ["EXTSTATE", "EXTENSIONS"]
+            .into_iter()
+            .filter_map(|block| child(&self.inner, block))
+            .find_map(|block| child(block, section))
+            .map(|section| section.values.as_slice())
     }
     pub fn tracks(&self) -> Vec<Track> {
         self.inner
             .values
             .iter()
             .filter_map(|e| e.as_object())
+            .filter(|o| Track::matches_object(o))
+            .cloned()
+            .map(Track::from_object_raw)
+            .collect()
+    }
+    /// [`Self::tracks`] borrowed from the project instead of cloned
+    pub fn track_refs(&self) -> impl Iterator<Item = &Track> + '_ {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Track::matches_object(o))
+            .map(Track::from_object_ref_raw)
+    }
+
+    /// tracks paired with their zero-based position among tracks (the index `AUXRECV` uses),
+    /// cloned one at a time as the iterator advances
+    pub fn tracks_enumerated(&self) -> impl Iterator<Item = (usize, Track)> + '_ {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Track::matches_object(o))
+            .cloned()
+            .map(Track::from_object_raw)
+            .enumerate()
+    }
+
+    /// first track named `name`. names are compared unquoted and need not be unique, see
+    /// [`Self::tracks_by_name`]. tracks without a readable `NAME` never match
+    pub fn track_by_name(&self, name: &str) -> Option<Track> {
+        self.track_refs()
+            .find(|track| has_name(track, name))
             .cloned()
-            .filter_map(|o| Track::from_object(o).ok())
+    }
+    /// position among tracks of the first track named `name`, as in [`Self::tracks_enumerated`]
+    pub fn track_index_by_name(&self, name: &str) -> Option<usize> {
+        self.track_refs().position(|track| has_name(track, name))
+    }
+    /// every track named `name`, in project order
+    pub fn tracks_by_name(&self, name: &str) -> Vec<Track> {
+        self.track_refs()
+            .filter(|track| has_name(track, name))
+            .cloned()
+            .collect()
+    }
+    /// runs `modify` on the first track named `name` in place, `None` when there is no such track
+    pub fn modify_track_by_name<T>(
+        &mut self,
+        name: &str,
+        modify: impl FnOnce(&mut Track) -> T,
+    ) -> Option<T> {
+        self.inner
+            .values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .filter(|object| Track::matches_object(object))
+            .find(|object| has_name(Track::from_object_ref_raw(object), name))
+            .map(|object| Track::with_as_object_mut(object, modify).expect("checked above"))
+    }
+    /// runs `modify` in place on the track whose `TRACKID` is `id`. neither this track nor the
+    /// others are cloned or moved
+    pub fn modify_track_by_id<T>(
+        &mut self,
+        id: &ReaperUid,
+        modify: impl FnOnce(&mut Track) -> T,
+    ) -> Result<T> {
+        self.inner
+            .values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .filter(|object| Track::matches_object(object))
+            .find(|object| Track::from_object_ref_raw(object).track_id() == Some(id))
+            .ok_or_else(|| error::Error::NoSuchTrack { uid: id.clone() })
+            .and_then(|object| Track::with_as_object_mut(object, modify))
+    }
+
+    /// every item paired with the position of its track, as in [`Self::tracks_enumerated`].
+    /// tracks come in file order and items in the order their track lists them, so sort by
+    /// [`Item::position`] for a timeline across tracks
+    pub fn all_items(&self) -> impl Iterator<Item = (usize, Item)> + '_ {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Track::matches_object(o))
+            .enumerate()
+            .flat_map(|(index, track)| {
+                track
+                    .values
+                    .iter()
+                    .filter_map(|e| e.as_object())
+                    .filter(|o| Item::matches_object(o))
+                    .cloned()
+                    .map(move |item| (index, Item::from_object_raw(item)))
+            })
+    }
+
+    /// every item with the track it is on, ordered by [`cmp_by_position`]. the sort is stable:
+    /// items starting at the same time stay in file order, that is by track and then in the
+    /// order their track lists them
+    pub fn items_sorted_by_position(&self) -> Vec<(Track, Item)> {
+        self.track_refs()
+            .flat_map(|track| track.item_refs().map(move |item| (track, item)))
+            .collect::<Vec<_>>()
+            .tap_mut(|items| items.sort_by(|(_, a), (_, b)| cmp_by_position(a, b)))
+            .into_iter()
+            .map(|(track, item)| (track.clone(), item.clone()))
             .collect()
     }
 
+    /// project sample rate in Hz, first value of `SAMPLERATE`
+    pub fn sample_rate(&self) -> Result<u32> {
+        const SAMPLERATE: &str = "SAMPLERATE";
+        let rate = required(SAMPLERATE, int_at(&self.inner, SAMPLERATE, 0))?;
+        u32::try_from(rate).map_err(|_| error::Error::OutOfRange {
+            field: SAMPLERATE,
+            value: rate,
+        })
+    }
+
+    /// end of the last item on the timeline in seconds, `0` for a project without items.
+    /// items without a readable `POSITION` or `LENGTH` are skipped
+    pub fn length(&self) -> f64 {
+        self.track_refs()
+            .flat_map(Track::item_refs)
+            .filter_map(|item| Some(item.position().ok()? + item.length().ok()?))
+            .fold(0., f64::max)
+    }
+
+    /// hands every track to `modifier` and puts whatever it returns where the first track was
+    /// (or before the first object, when there are no tracks yet). other entries stay where they
+    /// are. entries are moved, never cloned
     pub fn modify_tracks<F: FnOnce(Vec<Track>) -> Vec<Track>>(
         &mut self,
         modifier: F,
     ) -> Result<()> {
-        let value_index = || self.inner.values.iter().enumerate();
-        let original_index_start = value_index()
-            .find_map(|(index, entry)| entry.as_object().map(|_| index))
-            .or_else(|| value_index().last().map(|(index, _)| index))
+        let values = &mut self.inner.values;
+        let original_index_start = values
+            .iter()
+            .position(|entry| entry.as_object().is_some_and(Track::matches_object))
+            .or_else(|| values.iter().position(|entry| entry.as_object().is_some()))
+            .or_else(|| values.len().checked_sub(1))
             .ok_or(error::Error::EmptyProject)?;
-        let mut values = self.inner.values.clone();
-        let popped_tracks = {
-            values
-                .extract_if(|val| {
-                    val.as_object()
-                        .and_then(|inner| Track::from_object(inner.clone()).ok())
-                        .is_some()
-                })
-                .map(|inner| {
-                    inner
-                        .as_object()
-                        .cloned()
-                        .map(|inner| Track::from_object(inner).expect("this was checked above"))
-                        .expect("this was also checked above")
-                })
-                .collect::<Vec<_>>()
-        };
-        let new_tracks = modifier(popped_tracks);
-        new_tracks.into_iter().rev().for_each(|track| {
-            values.insert(original_index_start, Entry::Object(track.inner));
-        });
+        let (popped_tracks, rest): (Vec<_>, Vec<_>) = std::mem::take(values)
+            .into_iter()
+            .partition(|entry| entry.as_object().is_some_and(Track::matches_object));
+        *values = rest;
+        let new_tracks = popped_tracks
+            .into_iter()
+            .filter_map(|entry| entry.into_object().ok())
+            .map(Track::from_object_raw)
+            .collect::<Vec<_>>()
+            .pipe(modifier);
+        values.splice(
+            original_index_start..original_index_start,
+            new_tracks
+                .into_iter()
+                .map(|track| Entry::Object(track.inner)),
+        );
+        Ok(())
+    }
 
-        self.inner.values = values;
+    /// rebases every source path as if the project file moved from `old_dir` to `new_dir`,
+    /// see [`SourceWave::rebase_path`]
+    pub fn relocate(&mut self, old_dir: &Path, new_dir: &Path) -> Result<()> {
+        self.try_for_each_track_mut(|track| track.rebase_paths(old_dir, new_dir))
+    }
 
-        Ok(())
+    /// [`SourceWave::set_path_separator`] for every source in the project
+    pub fn set_path_separator(&mut self, separator: PathSeparator) -> Result<()> {
+        self.try_for_each_track_mut(|track| track.set_path_separator(separator))
+    }
+
+    fn try_for_each_track_mut(
+        &mut self,
+        mut f: impl FnMut(&mut Track) -> Result<()>,
+    ) -> Result<()> {
+        self.inner
+            .values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .filter(|object| Track::matches_object(object))
+            .try_for_each(|object| Track::with_as_object_mut(object, &mut f).and_then(|res| res))
+    }
+
+    /// files directly inside `media_dir` which are not referenced by any source of any take in
+    /// the project. `FILE` paths are relative to the project file, which the project itself
+    /// doesn't know about, so they are resolved against `project_dir`. files are compared by
+    /// identity on disk rather than by name, so case-insensitive filesystems and differently
+    /// spelled paths to the same file are handled
+    pub fn find_unused_media(&self, project_dir: &Path, media_dir: &Path) -> Vec<PathBuf> {
+        let referenced = self
+            .tracks()
+            .into_iter()
+            .flat_map(|track| track.items())
+            .flat_map(|item| item.takes())
+            .flat_map(|take| source_files(&take.entries))
+            .filter_map(|file| {
+                same_file::Handle::from_path(project_dir.join(file.replace('\\', "/"))).ok()
+            })
+            .collect::<HashSet<_>>();
+        std::fs::read_dir(media_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                same_file::Handle::from_path(path)
+                    .map_or(true, |handle| !referenced.contains(&handle))
+            })
+            .collect::<Vec<_>>()
+            .tap_mut(|unused| unused.sort())
     }
 }
 
@@ -161,6 +757,62 @@ impl ObjectWrapper for Track {
     }
 }
 
+impl ObjectWrapper for FxChain {
+    const ATTRIBUTE_NAME: &'static str = "FXCHAIN";
+
+    fn from_object_raw(inner: Object) -> Self {
+        Self { inner }
+    }
+    fn destroy(self) -> Object {
+        self.inner
+    }
+    fn from_object(inner: Object) -> Result<Self> {
+        match matches_attribute_name_ref(&inner, Self::MASTER_ATTRIBUTE_NAME) {
+            true => Ok(Self::from_object_raw(inner)),
+            false => assert_attribute_name(inner, Self::ATTRIBUTE_NAME).map(Self::from_object_raw),
+        }
+    }
+    fn matches_object(inner: &Object) -> bool {
+        matches_attribute_name_ref(inner, Self::ATTRIBUTE_NAME)
+            || matches_attribute_name_ref(inner, Self::MASTER_ATTRIBUTE_NAME)
+    }
+}
+
+impl ObjectWrapper for Envelope {
+    /// envelopes are named after their parameter, this only stands in for them in errors
+    const ATTRIBUTE_NAME: &'static str = "ENV";
+
+    fn from_object_raw(inner: Object) -> Self {
+        Self { inner }
+    }
+    fn destroy(self) -> Object {
+        self.inner
+    }
+    fn from_object(inner: Object) -> error::Result<Self> {
+        match Self::matches_object(&inner) {
+            true => Ok(Self::from_object_raw(inner)),
+            false => Err(error::Error::InvalidObject {
+                expected: AttributeName::new(Self::ATTRIBUTE_NAME.to_owned()),
+                got: inner.header.attribute,
+            }),
+        }
+    }
+    fn matches_object(inner: &Object) -> bool {
+        envelope::is_envelope_name(inner.header.attribute.as_ref())
+    }
+}
+
+impl ObjectWrapper for RenderCfg {
+    const ATTRIBUTE_NAME: &'static str = "RENDER_CFG";
+
+    fn from_object_raw(inner: Object) -> Self {
+        Self { inner }
+    }
+    fn destroy(self) -> Object {
+        self.inner
+    }
+}
+
 impl ObjectWrapper for Item {
     const ATTRIBUTE_NAME: &'static str = "ITEM";
 
@@ -183,12 +835,97 @@ impl ObjectWrapper for SourceWave {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
+/// borrowed tracks ([`ReaperProject::track_refs`]) are plain `&Track`s pointing into the project,
+/// so every read-only accessor works on them without cloning
+#[derive(PartialEq, Eq, Clone, AsMut, AsRef, RefCastCustom)]
+#[repr(transparent)]
 pub struct Track {
     inner: Object,
 }
 
 impl Track {
+    #[ref_cast_custom]
+    fn from_object_ref_raw(inner: &Object) -> &Self;
+    /// borrowing counterpart of [`ObjectWrapper::from_object`]
+    pub fn from_object_ref(inner: &Object) -> Result<&Self> {
+        match Self::matches_object(inner) {
+            true => Ok(Self::from_object_ref_raw(inner)),
+            false => Err(error::Error::InvalidObject {
+                expected: AttributeName::new(Self::ATTRIBUTE_NAME.to_owned()),
+                got: inner.header.attribute.clone(),
+            }),
+        }
+    }
+    /// this track as the contents of a `.RTrackTemplate` file
+    pub fn to_track_template_string(&self) -> Result<String> {
+        low_level::to_string_many(&[Entry::Object(self.inner.clone())]).map_err(Into::into)
+    }
+    /// tracks of a `.RTrackTemplate` file, which holds one or more `<TRACK>`s and nothing else
+    pub fn from_track_template_str(input: &str) -> Result<Vec<Track>> {
+        low_level::from_str_many(input)?
+            .into_iter()
+            .filter(|entry| !entry.is_blank())
+            .map(|entry| match entry {
+                Entry::Object(object) => Track::from_object(object),
+                // loose lines have no header, report their name instead
+                other => Err(error::Error::InvalidObject {
+                    expected: AttributeName::new(Self::ATTRIBUTE_NAME.to_owned()),
+                    got: other
+                        .into_line()
+                        .map(|line| line.attribute)
+                        .unwrap_or_else(|_| AttributeName::new(String::new())),
+                }),
+            })
+            .collect()
+    }
+    /// uid from the `<TRACK {...}>` header
+    pub fn uid(&self) -> Option<&ReaperUid> {
+        self.inner
+            .header
+            .values
+            .first()
+            .and_then(|value| value.as_reaper_uid())
+    }
+    /// uid from the `TRACKID` line, which REAPER keeps equal to [`Self::uid`]
+    pub fn track_id(&self) -> Option<&ReaperUid> {
+        self.inner
+            .single_attribute("TRACKID")
+            .and_then(|value| value.as_reaper_uid())
+    }
+    /// deep clone of this track with every [`ReaperUid`] under it (track id, item guids, fx ids...)
+    /// replaced by a freshly generated one. a uid appearing more than once (like the header and
+    /// `TRACKID`) is replaced consistently, so references within the track stay intact
+    pub fn duplicate(&self) -> Track {
+        let mut replacements = HashMap::new();
+        self.clone().tap_mut(|duplicate| {
+            duplicate.inner.for_each_attribute_mut(&mut |attribute| {
+                if let Attribute::ReaperUid(uid) = attribute {
+                    *uid = replacements
+                        .entry(uid.clone())
+                        .or_insert_with(ReaperUid::random)
+                        .clone();
+                }
+            })
+        })
+    }
+    /// [`SourceWave::rebase_path`] for every source of every item on this track
+    pub fn rebase_paths(&mut self, old_dir: &Path, new_dir: &Path) -> Result<()> {
+        self.modify_items(|item| {
+            item.with_source_waves_mut(|source| source.rebase_path(old_dir, new_dir))
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+    /// [`SourceWave::set_path_separator`] for every source of every item on this track
+    pub fn set_path_separator(&mut self, separator: PathSeparator) -> Result<()> {
+        self.modify_items(|item| {
+            item.with_source_waves_mut(|source| source.set_path_separator(separator))
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
     pub fn modify_items<T>(&mut self, mut modify_items: impl FnMut(&mut Item) -> T) -> Vec<T> {
         self.inner
             .values
@@ -198,37 +935,682 @@ impl Track {
             .map(|o| Item::with_as_object_mut(o, &mut modify_items).expect("checked above"))
             .collect()
     }
-    pub fn items(&self) -> Vec<Item> {
-        self.inner
-            .values
-            .iter()
-            .filter_map(|e| e.as_object())
-            .cloned()
-            .filter_map(|item| Item::from_object(item).ok())
-            .collect()
+    /// track pan law (`PANLAW`) as a linear gain, `None` when the track follows the project default
+    pub fn pan_law(&self) -> Result<Option<f64>> {
+        number_at(&self.inner, "PANLAW", 0).transpose()
     }
-    pub fn name(&self) -> Result<String> {
-        const NAME: &str = "NAME";
-        self.inner
-            .values
-            .iter()
-            .find_map(|entry| {
-                entry
-                    .as_line()
-                    .and_then(|line| line.attribute.as_ref().eq(NAME).then_some(&line.values))
+    pub fn set_pan_law(&mut self, pan_law: Option<f64>) {
+        set_line(
+            &mut self.inner,
+            "PANLAW",
+            pan_law.map(|pan_law| vec![Attribute::Float(OrderedFloat(pan_law))]),
+        )
+    }
+    /// raw `PANLAWFLAGS` (pan law mode bits), `None` when the track follows the project default
+    pub fn pan_law_flags(&self) -> Result<Option<i64>> {
+        int_at(&self.inner, "PANLAWFLAGS", 0).transpose()
+    }
+    pub fn set_pan_law_flags(&mut self, flags: Option<i64>) {
+        set_line(
+            &mut self.inner,
+            "PANLAWFLAGS",
+            flags.map(|flags| vec![Attribute::Int(Int(flags))]),
+        )
+    }
+    /// fixed item lane layout, `None` for tracks without fixed lanes (and pre-7 projects)
+    pub fn fixed_lanes(&self) -> Result<Option<FixedLanes>> {
+        const FIXEDLANES: &str = "FIXEDLANES";
+        let Some(values) = self.inner.attributes(FIXEDLANES) else {
+            return Ok(None);
+        };
+        (0..values.len())
+            .map(|index| required(FIXEDLANES, int_at(&self.inner, FIXEDLANES, index)))
+            .collect::<Result<Vec<_>>>()?
+            .split_first()
+            .map(|(flags, extra)| FixedLanes {
+                flags: *flags,
+                extra: extra.to_vec(),
+            })
+            .ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(FIXEDLANES.to_owned()),
+            })
+            .map(Some)
+    }
+    /// whether a spacer is drawn above this track (`SPACER 1`)
+    pub fn has_spacer(&self) -> Result<bool> {
+        int_at(&self.inner, "SPACER", 0)
+            .transpose()
+            .map(|spacer| spacer.unwrap_or(0) != 0)
+    }
+    /// REAPER only writes `SPACER` for tracks that have one, so removing the spacer drops the line
+    pub fn set_spacer(&mut self, spacer: bool) {
+        set_line(
+            &mut self.inner,
+            "SPACER",
+            spacer.then(|| vec![Attribute::Int(Int(1))]),
+        )
+    }
+    /// `REC <armed> <input> <monitoring> ...`, only the fields below are interpreted, the
+    /// setters leave the rest (their number differs between REAPER versions) untouched
+    pub fn record_arm(&self) -> Result<bool> {
+        required("REC", int_at(&self.inner, "REC", 0)).map(|armed| armed != 0)
+    }
+    pub fn set_record_arm(&mut self, armed: bool) -> Result<()> {
+        set_attribute_at(&mut self.inner, "REC", 0, Attribute::Int(Int(armed as i64)))
+    }
+    /// raw record input, REAPER encodes the input kind (mono, stereo, midi...) in its bits
+    pub fn record_input(&self) -> Result<i64> {
+        required("REC", int_at(&self.inner, "REC", 1))
+    }
+    pub fn record_monitoring(&self) -> Result<RecordMonitoring> {
+        required("REC", int_at(&self.inner, "REC", 2)).map(RecordMonitoring::from)
+    }
+    pub fn set_record_monitoring(&mut self, monitoring: RecordMonitoring) -> Result<()> {
+        set_attribute_at(
+            &mut self.inner,
+            "REC",
+            2,
+            Attribute::Int(Int(monitoring.into())),
+        )
+    }
+    /// raw `VU` value, the track meter configuration
+    pub fn metering_config(&self) -> Result<i64> {
+        required("VU", int_at(&self.inner, "VU", 0))
+    }
+    /// writes `VU`, adding the line when the track has none
+    pub fn set_metering_config(&mut self, config: impl Into<i64>) -> Result<()> {
+        let config = Attribute::Int(Int(config.into()));
+        match self.inner.attributes("VU").is_some() {
+            true => set_attribute_at(&mut self.inner, "VU", 0, config),
+            false => {
+                set_line(&mut self.inner, "VU", Some(vec![config]));
+                Ok(())
+            }
+        }
+    }
+    pub fn metering_mode(&self) -> Result<MeteringMode> {
+        self.metering_config().map(MeteringMode::from)
+    }
+    pub fn fx_chain(&self) -> Option<FxChain> {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .find(|o| matches_attribute_name_ref(o, FxChain::ATTRIBUTE_NAME))
+            .cloned()
+            .map(FxChain::from_object_raw)
+    }
+    /// appends the plugins of `other`'s fx chain to this track's, creating the chain when
+    /// there is none. the copies get fresh `FXID`s (and uids of their parameter envelopes), so
+    /// REAPER does not take them for the same instances. the chain settings (window position,
+    /// last selected plugin) of this track are kept
+    pub fn copy_fx_from(&mut self, other: &Track) -> Result<()> {
+        let Some(source) = other.fx_chain() else {
+            return Ok(());
+        };
+        // every plugin starts with its `BYPASS` line, the lines before the first are chain settings
+        let mut plugins = Object {
+            header: source.inner.header.clone(),
+            values: source
+                .inner
+                .values
+                .into_iter()
+                .skip_while(|entry| {
+                    entry
+                        .as_line()
+                        .is_none_or(|line| line.attribute.as_ref() != "BYPASS")
+                })
+                .collect(),
+        };
+        if plugins.values.is_empty() {
+            return Ok(());
+        }
+        let mut replacements = HashMap::new();
+        plugins.for_each_attribute_mut(&mut |attribute| {
+            if let Attribute::ReaperUid(uid) = attribute {
+                *uid = replacements
+                    .entry(uid.clone())
+                    .or_insert_with(ReaperUid::random)
+                    .clone();
+            }
+        });
+        let chain = self
+            .inner
+            .values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .find(|o| matches_attribute_name_ref(o, FxChain::ATTRIBUTE_NAME));
+        match chain {
+            Some(chain) => chain.values.extend(plugins.values),
+            None => {
+                let settings = ["SHOW", "LASTSEL", "DOCKED"].map(|setting| {
+                    Entry::Line(Line {
+                        attribute: AttributeName::new(setting.to_owned()),
+                        values: vec![Attribute::Int(Int(0))],
+                    })
+                });
+                let chain = Object {
+                    header: Line {
+                        attribute: AttributeName::new(FxChain::ATTRIBUTE_NAME.to_owned()),
+                        values: vec![],
+                    },
+                    values: settings.into_iter().chain(plugins.values).collect(),
+                };
+                // where REAPER writes it, after the track's lines and before envelopes and items
+                let position = self
+                    .inner
+                    .values
+                    .iter()
+                    .position(|entry| entry.as_object().is_some())
+                    .unwrap_or(self.inner.values.len());
+                self.inner.values.insert(position, Entry::Object(chain));
+            }
+        }
+        Ok(())
+    }
+    pub fn items(&self) -> Vec<Item> {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Item::matches_object(o))
+            .cloned()
+            .map(Item::from_object_raw)
+            .collect()
+    }
+    /// [`Self::items`] borrowed from the track instead of cloned
+    pub fn item_refs(&self) -> impl Iterator<Item = &Item> + '_ {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Item::matches_object(o))
+            .map(Item::from_object_ref_raw)
+    }
+    /// name, channel count, volume and pan, mute and solo, color and item count in one go.
+    /// lines REAPER may leave out (`NCHAN`, `MUTESOLO`, `PEAKCOL`) fall back to its defaults
+    pub fn info(&self) -> Result<TrackInfo> {
+        /// set on `PEAKCOL` values that are custom colors
+        const CUSTOM_COLOR: i64 = 0x1000000;
+        Ok(TrackInfo {
+            name: self.name_opt(),
+            channel_count: int_at(&self.inner, "NCHAN", 0).transpose()?.unwrap_or(2) as usize,
+            volume: required("VOLPAN", number_at(&self.inner, "VOLPAN", 0))?,
+            pan: required("VOLPAN", number_at(&self.inner, "VOLPAN", 1))?,
+            muted: int_at(&self.inner, "MUTESOLO", 0)
+                .transpose()?
+                .is_some_and(|mute| mute != 0),
+            soloed: int_at(&self.inner, "MUTESOLO", 1)
+                .transpose()?
+                .is_some_and(|solo| solo != 0),
+            color: int_at(&self.inner, "PEAKCOL", 0)
+                .transpose()?
+                .filter(|color| color & CUSTOM_COLOR != 0),
+            item_count: self
+                .inner
+                .values
+                .iter()
+                .filter_map(|e| e.as_object())
+                .filter(|o| Item::matches_object(o))
+                .count(),
+        })
+    }
+    /// sets `NAME`, quoted as needed, see [`ReaperString::new`]
+    pub fn set_name(&mut self, name: &str) {
+        set_line(
+            &mut self.inner,
+            "NAME",
+            Some(vec![Attribute::String(ReaperString::new(name))]),
+        )
+    }
+    /// name with the quotes stripped. names REAPER writes unquoted which happen to parse as
+    /// something other than a string (`NAME 2`) are returned as written
+    pub fn name(&self) -> Result<String> {
+        const NAME: &str = "NAME";
+        self.inner
+            .values
+            .iter()
+            .find_map(|entry| {
+                entry
+                    .as_line()
+                    .and_then(|line| line.attribute.as_ref().eq(NAME).then_some(&line.values))
             })
             .and_then(|values| values.iter().next())
             .ok_or_else(|| error::Error::MissingAttribute {
                 attribute: AttributeName::new(NAME.to_owned()),
             })
-            .and_then(|attribute| attribute.serialize_inline().map_err(Into::into))
+            .and_then(|attribute| match attribute.as_str() {
+                Some(name) => Ok(name.to_owned()),
+                None => attribute.serialize_inline().map_err(Into::into),
+            })
+    }
+    /// like [`Track::name`], but `None` for unnamed tracks: a missing `NAME` or `NAME ""`, both
+    /// of which REAPER writes
+    pub fn name_opt(&self) -> Option<String> {
+        self.name().ok().filter(|name| !name.is_empty())
+    }
+}
+
+fn has_name(track: &Track, name: &str) -> bool {
+    track.name().is_ok_and(|track_name| track_name == name)
+}
+
+/// the `REAPER_PROJECT <format> "<reaper version>/<platform>" <saved at>` header
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectVersion {
+    /// version of the file format, `0.1` for every REAPER so far
+    pub format: f64,
+    /// version of REAPER that saved the file, e.g. `6.80` or `7.0rc1`
+    pub reaper_version: String,
+    /// e.g. `linux-x86_64` or `win64`, missing in files from some older versions
+    pub platform: Option<String>,
+    /// unix timestamp of the save, in seconds
+    pub saved_at: i64,
+}
+
+impl ProjectVersion {
+    /// numeric part of [`Self::reaper_version`] (`7.0` for `7.0rc1`), for telling whether the
+    /// file comes from a newer REAPER. REAPER numbers its versions as decimals, `6.8` is `6.80`
+    pub fn reaper_version_number(&self) -> Option<f64> {
+        let end = self
+            .reaper_version
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(self.reaper_version.len());
+        self.reaper_version[..end].parse().ok()
+    }
+}
+
+/// overview of a track, see [`Track::info`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct TrackInfo {
+    /// see [`Track::name_opt`]
+    pub name: Option<String>,
+    /// `NCHAN`
+    pub channel_count: usize,
+    /// linear gain, `1` is 0dB
+    pub volume: f64,
+    /// `-1` (left) to `1` (right)
+    pub pan: f64,
+    pub muted: bool,
+    pub soloed: bool,
+    /// raw `PEAKCOL` in the native color format of the platform that saved the file,
+    /// `None` when the track has no custom color
+    pub color: Option<i64>,
+    pub item_count: usize,
+}
+
+/// master track settings, read from the `MASTER*` lines of the project
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasterTrack {
+    /// linear gain, `1` is 0dB
+    pub volume: f64,
+    /// `-1` (left) to `1` (right)
+    pub pan: f64,
+    pub channel_count: usize,
+    pub fx_chain: Option<FxChain>,
+}
+
+/// input monitoring of a track, third value of `REC`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordMonitoring {
+    Off,
+    On,
+    /// tape style, monitoring only while not playing
+    NotWhenPlaying,
+    /// a value this crate does not know about, kept as is
+    Other(i64),
+}
+
+impl From<i64> for RecordMonitoring {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::Off,
+            1 => Self::On,
+            2 => Self::NotWhenPlaying,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<RecordMonitoring> for i64 {
+    fn from(value: RecordMonitoring) -> Self {
+        match value {
+            RecordMonitoring::Off => 0,
+            RecordMonitoring::On => 1,
+            RecordMonitoring::NotWhenPlaying => 2,
+            RecordMonitoring::Other(other) => other,
+        }
+    }
+}
+
+/// track meter configuration, the `VU` value (`I_VUMODE` in the REAPER api)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeteringMode {
+    StereoPeaks,
+    /// what REAPER writes for a freshly created track
+    MultichannelPeaks,
+    StereoRms,
+    CombinedRms,
+    /// LUFS-M
+    LufsMomentary,
+    /// LUFS-S, the readout shows the maximum
+    LufsShortTermMax,
+    /// LUFS-S, the readout shows the current value
+    LufsShortTerm,
+    /// anything else, kept as is. this includes modes combined with the flags `1` (metering
+    /// disabled) and `32` (LUFS calculated on channels 1+2 only)
+    Other(i64),
+}
+
+impl From<i64> for MeteringMode {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::StereoPeaks,
+            2 => Self::MultichannelPeaks,
+            4 => Self::StereoRms,
+            8 => Self::CombinedRms,
+            12 => Self::LufsMomentary,
+            16 => Self::LufsShortTermMax,
+            20 => Self::LufsShortTerm,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<MeteringMode> for i64 {
+    fn from(value: MeteringMode) -> Self {
+        match value {
+            MeteringMode::StereoPeaks => 0,
+            MeteringMode::MultichannelPeaks => 2,
+            MeteringMode::StereoRms => 4,
+            MeteringMode::CombinedRms => 8,
+            MeteringMode::LufsMomentary => 12,
+            MeteringMode::LufsShortTermMax => 16,
+            MeteringMode::LufsShortTerm => 20,
+            MeteringMode::Other(other) => other,
+        }
+    }
+}
+
+/// item fade, `FADEIN`/`FADEOUT <shape> <length> <auto length> _ _ <direction> _`.
+/// lengths are in seconds. the auto length is what REAPER applies while auto-fades are on
+/// and is `0` for a fade set by hand, in which case the length is used. direction bends the
+/// curve, from `-1` to `1`. values not listed are kept as they are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fade {
+    field: &'static str,
+    values: Vec<Attribute>,
+}
+
+impl Fade {
+    const SHAPE: usize = 0;
+    const LENGTH: usize = 1;
+    const AUTO_LENGTH: usize = 2;
+    const DIRECTION: usize = 5;
+
+    fn value(&self, index: usize) -> Result<&Attribute> {
+        self.values
+            .get(index)
+            .ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(self.field.to_owned()),
+            })
+    }
+    fn set(&mut self, index: usize, value: Attribute) -> Result<()> {
+        self.values
+            .get_mut(index)
+            .map(|attribute| *attribute = value)
+            .ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(self.field.to_owned()),
+            })
+    }
+    pub fn shape(&self) -> Result<i64> {
+        self.value(Self::SHAPE)
+            .and_then(|value| as_int(self.field, value))
+    }
+    pub fn set_shape(&mut self, shape: i64) -> Result<()> {
+        self.set(Self::SHAPE, Attribute::Int(Int(shape)))
+    }
+    pub fn length(&self) -> Result<f64> {
+        self.value(Self::LENGTH)
+            .and_then(|value| as_number(self.field, value))
+    }
+    pub fn set_length(&mut self, length: f64) -> Result<()> {
+        self.set(Self::LENGTH, Attribute::Float(OrderedFloat(length)))
+    }
+    pub fn auto_length(&self) -> Result<f64> {
+        self.value(Self::AUTO_LENGTH)
+            .and_then(|value| as_number(self.field, value))
+    }
+    /// length REAPER actually uses
+    pub fn effective_length(&self) -> Result<f64> {
+        match self.auto_length()? {
+            auto_length if auto_length != 0. => Ok(auto_length),
+            _ => self.length(),
+        }
+    }
+    pub fn is_manual(&self) -> Result<bool> {
+        self.auto_length().map(|auto_length| auto_length == 0.)
+    }
+    /// a manual fade keeps its current effective length, an automatic one starts out
+    /// with the manual length
+    pub fn set_manual(&mut self, manual: bool) -> Result<()> {
+        match manual {
+            true => {
+                let length = self.effective_length()?;
+                self.set_length(length)?;
+                self.set(Self::AUTO_LENGTH, Attribute::Int(Int(0)))
+            }
+            false => {
+                let length = self.length()?;
+                self.set(Self::AUTO_LENGTH, Attribute::Float(OrderedFloat(length)))
+            }
+        }
+    }
+    pub fn direction(&self) -> Result<f64> {
+        self.value(Self::DIRECTION)
+            .and_then(|value| as_number(self.field, value))
+    }
+    pub fn set_direction(&mut self, direction: f64) -> Result<()> {
+        self.set(Self::DIRECTION, Attribute::Float(OrderedFloat(direction)))
+    }
+}
+
+/// item playback rate,
+/// `PLAYRATE <rate> <preserve pitch> <pitch> <pitch shifter> <stretch marker mode> ...`.
+/// the values after the stretch marker mode (stretch marker fade size, in seconds) are kept as
+/// written
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayRate {
+    pub rate: f64,
+    pub preserve_pitch: bool,
+    pub pitch_semitones: f64,
+    /// pitch shifter and its mode packed together, [`PlayRate::PROJECT_DEFAULT`] for the
+    /// project's default shifter
+    pub pitch_mode: i64,
+    pub stretch_mode: i64,
+    pub extra: Vec<Attribute>,
+}
+
+impl PlayRate {
+    const FIELD: &'static str = "PLAYRATE";
+    /// `pitch_mode` of items using the project's default pitch shifter
+    pub const PROJECT_DEFAULT: i64 = -1;
+}
+
+impl Default for PlayRate {
+    /// what REAPER writes for a new item
+    fn default() -> Self {
+        Self {
+            rate: 1.,
+            preserve_pitch: false,
+            pitch_semitones: 0.,
+            pitch_mode: Self::PROJECT_DEFAULT,
+            stretch_mode: 0,
+            extra: vec![Attribute::Float(OrderedFloat(0.0025))],
+        }
+    }
+}
+
+impl TryFrom<&[Attribute]> for PlayRate {
+    type Error = error::Error;
+
+    /// values of a `PLAYRATE` line. older files stop after the pitch shifter or the pitch,
+    /// the missing modes are read as REAPER's defaults
+    fn try_from(values: &[Attribute]) -> Result<Self> {
+        let value = |index: usize| values.get(index);
+        let required = |index| {
+            value(index).ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(Self::FIELD.to_owned()),
+            })
+        };
+        let int_or = |index, default| {
+            value(index)
+                .map(|value| as_int(Self::FIELD, value))
+                .unwrap_or(Ok(default))
+        };
+        Ok(Self {
+            rate: required(0).and_then(|value| as_number(Self::FIELD, value))?,
+            preserve_pitch: required(1).and_then(|value| as_int(Self::FIELD, value))? != 0,
+            pitch_semitones: required(2).and_then(|value| as_number(Self::FIELD, value))?,
+            pitch_mode: int_or(3, Self::PROJECT_DEFAULT)?,
+            stretch_mode: int_or(4, 0)?,
+            extra: values.iter().skip(5).cloned().collect(),
+        })
+    }
+}
+
+impl From<PlayRate> for Vec<Attribute> {
+    fn from(playrate: PlayRate) -> Self {
+        [
+            Attribute::Float(OrderedFloat(playrate.rate)),
+            Attribute::Int(Int(playrate.preserve_pitch as i64)),
+            Attribute::Float(OrderedFloat(playrate.pitch_semitones)),
+            Attribute::Int(Int(playrate.pitch_mode)),
+            Attribute::Int(Int(playrate.stretch_mode)),
+        ]
+        .into_iter()
+        .chain(playrate.extra)
+        .collect()
+    }
+}
+
+/// fixed item lanes of a track, `FIXEDLANES <flags> ...` (REAPER 7+).
+/// the first value is a bit field, its lowest bit enables fixed lanes. the values after it are
+/// kept as written, REAPER does not document them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedLanes {
+    pub flags: i64,
+    pub extra: Vec<i64>,
+}
+
+impl FixedLanes {
+    pub fn enabled(&self) -> bool {
+        self.flags & 1 != 0
     }
 }
 
+/// effects of a track (`<FXCHAIN>`) or of the master track (`<MASTERFXLIST>`)
+#[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
+pub struct FxChain {
+    inner: Object,
+}
+
+impl FxChain {
+    pub const MASTER_ATTRIBUTE_NAME: &'static str = "MASTERFXLIST";
+}
+
+/// output format of the project render, `<RENDER_CFG>` holding a base64 chunk that starts
+/// with a four byte magic naming the format backwards, followed by its settings
+#[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
+pub struct RenderCfg {
+    inner: Object,
+}
+
+/// automation envelope of a track, take or the project, see [`envelope`]
 #[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
+pub struct Envelope {
+    inner: Object,
+}
+
+/// format [`RenderCfg`] renders to, told apart by the magic at the start of its chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Wav,
+    Flac,
+    Mp3,
+    /// video and audio formats rendered through FFmpeg
+    Ffmpeg,
+    Other([u8; 4]),
+}
+
+impl From<[u8; 4]> for RenderFormat {
+    fn from(magic: [u8; 4]) -> Self {
+        match &magic {
+            b"evaw" => Self::Wav,
+            b"calf" => Self::Flac,
+            b"l3pm" => Self::Mp3,
+            b"ffmp" => Self::Ffmpeg,
+            _ => Self::Other(magic),
+        }
+    }
+}
+
+impl RenderCfg {
+    /// the decoded chunk, magic included
+    pub fn data(&self) -> Result<Vec<u8>> {
+        self.inner.chunk().map_err(Into::into)
+    }
+    /// replaces the whole chunk, REAPER expects it to start with a magic it knows
+    pub fn set_data(&mut self, data: &[u8]) {
+        self.inner.set_chunk(data)
+    }
+    pub fn magic(&self) -> Result<[u8; 4]> {
+        let data = self.data()?;
+        data.get(..4)
+            .and_then(|magic| magic.try_into().ok())
+            .ok_or(error::Error::ChunkTooShort {
+                expected: 4,
+                found: data.len(),
+            })
+    }
+    pub fn format(&self) -> Result<RenderFormat> {
+        self.magic().map(RenderFormat::from)
+    }
+}
+
+/// orders items by [`Item::position`], for use with `sort_by`. items whose position cannot
+/// be read come first
+pub fn cmp_by_position(a: &Item, b: &Item) -> std::cmp::Ordering {
+    match (a.position().ok(), b.position().ok()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, AsMut, AsRef, RefCastCustom)]
+#[repr(transparent)]
 pub struct Item {
     inner: Object,
 }
+/// separator written between the components of source paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSeparator {
+    /// `/`, understood by REAPER on every platform
+    Slash,
+    /// `\`, what REAPER writes on windows
+    Backslash,
+}
+
+impl PathSeparator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PathSeparator::Slash => "/",
+            PathSeparator::Backslash => "\\",
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, AsMut, AsRef)]
 pub struct SourceWave {
     inner: Object,
@@ -242,24 +1624,55 @@ impl SourceWave {
                 other => Err(error::Error::InvalidAttributeType {
                     field: "FILE",
                     expected: AttributeKind::String,
-                    found: AttributeKind::from(&*other),
+                    found: other.kind(),
                 }),
             })
         })
     }
+    /// rewrites `FILE` as if the project file moved from `old_dir` to `new_dir`. relative paths
+    /// are resolved against `old_dir` and stay relative if they land inside `new_dir`,
+    /// absolute paths are left untouched
+    pub fn rebase_path(&mut self, old_dir: &Path, new_dir: &Path) -> Result<()> {
+        match self.file_mut() {
+            None => Ok(()),
+            Some(file) => file.map(|file| {
+                let path = Path::new(file.as_str());
+                if path.is_absolute() {
+                    return;
+                }
+                let resolved = old_dir.join(path);
+                *file = resolved
+                    .strip_prefix(new_dir)
+                    .unwrap_or(&resolved)
+                    .display()
+                    .to_string();
+            }),
+        }
+    }
+    /// rewrites every separator in `FILE` to `separator`, whichever platform wrote it
+    pub fn set_path_separator(&mut self, separator: PathSeparator) -> Result<()> {
+        match self.file_mut() {
+            None => Ok(()),
+            Some(file) => file.map(|file| {
+                *file = file.replace(['/', '\\'], separator.as_str());
+            }),
+        }
+    }
     pub fn file(&self) -> Option<Result<&str>> {
         self.inner.single_attribute("FILE").map(|out| match out {
             low_level::Attribute::String(s) => Ok(s.as_ref().as_str()),
             other => Err(error::Error::InvalidAttributeType {
                 field: "FILE",
                 expected: AttributeKind::String,
-                found: AttributeKind::from(other),
+                found: other.kind(),
             }),
         })
     }
 }
 
 impl Item {
+    #[ref_cast_custom]
+    fn from_object_ref_raw(inner: &Object) -> &Self;
     pub fn with_source_waves_mut<T, F: FnOnce(&mut SourceWave) -> T + Copy>(
         &mut self,
         with_source_wave_mut: F,
@@ -275,12 +1688,140 @@ impl Item {
             .collect()
     }
 
+    /// source of the first take, see [`Self::takes`] for the others
     pub fn source_wave(&self) -> Option<SourceWave> {
         self.inner
             .values
             .iter()
             .filter_map(|e| e.as_object())
-            .find_map(|o| SourceWave::from_object(o.clone()).ok())
+            .find(|o| SourceWave::matches_object(o))
+            .cloned()
+            .map(SourceWave::from_object_raw)
+    }
+
+    fn fade(&self, field: &'static str) -> Option<Fade> {
+        self.inner.attributes(field).map(|values| Fade {
+            field,
+            values: values.clone(),
+        })
+    }
+    pub fn fade_in(&self) -> Option<Fade> {
+        self.fade("FADEIN")
+    }
+    pub fn fade_out(&self) -> Option<Fade> {
+        self.fade("FADEOUT")
+    }
+    /// writes `fade` back as a fade in, whichever item or line it was read from
+    pub fn set_fade_in(&mut self, fade: Fade) {
+        set_line(&mut self.inner, "FADEIN", Some(fade.values))
+    }
+    pub fn set_fade_out(&mut self, fade: Fade) {
+        set_line(&mut self.inner, "FADEOUT", Some(fade.values))
+    }
+
+    /// item start on the timeline, in seconds
+    pub fn position(&self) -> Result<f64> {
+        required("POSITION", number_at(&self.inner, "POSITION", 0))
+    }
+
+    /// item length on the timeline, in seconds
+    pub fn length(&self) -> Result<f64> {
+        required("LENGTH", number_at(&self.inner, "LENGTH", 0))
+    }
+
+    /// first value of `MUTE`. missing means not muted
+    pub fn is_muted(&self) -> Result<bool> {
+        int_at(&self.inner, "MUTE", 0)
+            .unwrap_or(Ok(0))
+            .map(|muted| muted != 0)
+    }
+    /// sets the first value of `MUTE`, keeping the ones after it
+    pub fn set_muted(&mut self, muted: bool) {
+        let muted = Attribute::Int(Int(muted as i64));
+        if set_attribute_at(&mut self.inner, "MUTE", 0, muted.clone()).is_err() {
+            set_line(
+                &mut self.inner,
+                "MUTE",
+                Some(vec![muted, Attribute::Int(Int(0))]),
+            )
+        }
+    }
+
+    /// `SEL`, missing means not selected
+    pub fn is_selected(&self) -> Result<bool> {
+        int_at(&self.inner, "SEL", 0)
+            .unwrap_or(Ok(0))
+            .map(|selected| selected != 0)
+    }
+    pub fn set_selected(&mut self, selected: bool) {
+        set_line(
+            &mut self.inner,
+            "SEL",
+            Some(vec![Attribute::Int(Int(selected as i64))]),
+        )
+    }
+
+    /// snap offset from the item start (`SNAPOFFS`), in seconds. missing means `0`
+    pub fn snap_offset(&self) -> Result<f64> {
+        number_at(&self.inner, "SNAPOFFS", 0).unwrap_or(Ok(0.))
+    }
+    pub fn set_snap_offset(&mut self, snap_offset: f64) {
+        set_line(
+            &mut self.inner,
+            "SNAPOFFS",
+            Some(vec![Attribute::Float(OrderedFloat(snap_offset))]),
+        )
+    }
+
+    /// offset into the source media (`SOFFS`), in seconds. missing means `0`
+    pub fn source_offset(&self) -> Result<f64> {
+        number_at(&self.inner, "SOFFS", 0).unwrap_or(Ok(0.))
+    }
+    /// sets the offset into the source media of the first take. trimming the head of an item
+    /// means moving `POSITION` and `SOFFS` forward and shortening `LENGTH` by the same amount
+    pub fn set_source_offset(&mut self, source_offset: f64) {
+        set_line(
+            &mut self.inner,
+            "SOFFS",
+            Some(vec![Attribute::Float(OrderedFloat(source_offset))]),
+        )
+    }
+
+    /// playback rate (first value of `PLAYRATE`). missing means `1`
+    pub fn play_rate(&self) -> Result<f64> {
+        number_at(&self.inner, "PLAYRATE", 0).unwrap_or(Ok(1.))
+    }
+
+    /// how many seconds of source media the item plays through, `LENGTH * PLAYRATE`
+    pub fn source_consumed_length(&self) -> Result<f64> {
+        Ok(self.length()? * self.play_rate()?)
+    }
+
+    /// the whole `PLAYRATE` line, see [`Item::play_rate`] for just the rate. missing means
+    /// [`PlayRate::default`]
+    pub fn playrate(&self) -> Result<PlayRate> {
+        self.inner
+            .attributes(PlayRate::FIELD)
+            .map_or(Ok(PlayRate::default()), |values| {
+                PlayRate::try_from(values.as_slice())
+            })
+    }
+    pub fn set_playrate(&mut self, playrate: PlayRate) {
+        set_line(&mut self.inner, PlayRate::FIELD, Some(playrate.into()))
+    }
+
+    /// item volume (first value of `VOLPAN`), linear gain where `1` is 0dB
+    pub fn volume(&self) -> Result<f64> {
+        required("VOLPAN", number_at(&self.inner, "VOLPAN", 0))
+    }
+    pub fn set_volume(&mut self, volume: f64) -> Result<()> {
+        set_number_at(&mut self.inner, "VOLPAN", 0, volume)
+    }
+
+    /// whether this item reads past the end of a source `source_duration` seconds long,
+    /// in which case REAPER loops or plays silence
+    pub fn exceeds_source(&self, source_duration: f64) -> Result<bool> {
+        Ok(self.source_offset()? + self.source_consumed_length()? > source_duration)
     }
 }
 
@@ -288,6 +1829,8 @@ impl Item {
 mod tests {
     use super::*;
     const EXAMPLE_1: &str = include_str!("../test_data/barbarah-anne.rpp");
+    const WEIRD_TRACK: &str = include_str!("../test_data/weird-track.rpp");
+    const WEIRD_TRACK_2: &str = include_str!("../test_data/weird-track-2.rpp");
 
     #[test]
     fn test_extract_tracks() -> Result<()> {
@@ -298,4 +1841,1208 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_with_as_object_mut() -> Result<()> {
+        let mut object = low_level::from_str(WEIRD_TRACK_2)?;
+        let original = object.clone();
+        assert!(matches!(
+            Item::with_as_object_mut(&mut object, |_| ()),
+            Err(error::Error::InvalidObject { .. })
+        ));
+        assert_eq!(object, original);
+        Track::with_as_object_mut(&mut object, |track| track.set_name("renamed"))?;
+        assert_eq!(Track::from_object(object)?.name()?, "renamed");
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_object_mut() -> Result<()> {
+        let mut track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        let line = low_level::parse_line("LAYOUTS \"a layout\" \"\"")?;
+        track.with_object_mut(|object| object.values.push(Entry::Line(line.clone())));
+        assert_eq!(
+            track.as_object().values.last().and_then(Entry::as_line),
+            Some(&line)
+        );
+        assert_eq!(track.as_object().attributes("LAYOUTS"), Some(&line.values));
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_info() -> Result<()> {
+        let tracks = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks();
+        let info = tracks[0].info()?;
+        assert_eq!(info.name, Some(tracks[0].name()?));
+        assert_eq!(info.channel_count, 2);
+        assert_eq!(info.volume, 0.65859561281431);
+        assert_eq!(info.pan, 0.);
+        assert!(!info.muted && !info.soloed);
+        assert_eq!(info.color, None);
+        assert_eq!(info.item_count, tracks[0].items().len());
+
+        let weird_track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        let info = weird_track.info()?;
+        assert_eq!(info.name.as_deref(), Some("GTX PRZEMEK"));
+        assert_eq!(info.color, Some(25362292));
+        assert_eq!(info.item_count, 1);
+
+        let minimal = Track::from_object(low_level::from_str(
+            "<TRACK\r\n  VOLPAN 1 -0.5\r\n  MUTESOLO 1 2 0\r\n>",
+        )?)?
+        .info()?;
+        assert_eq!(
+            (
+                minimal.name,
+                minimal.channel_count,
+                minimal.pan,
+                minimal.muted,
+                minimal.soloed
+            ),
+            (None, 2, -0.5, true, true)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_name() -> Result<()> {
+        let mut track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        for name in ["drums", "GTX PRZEMEK 2", "12\" snare", "2"] {
+            track.set_name(name);
+            assert_eq!(track.name()?, name);
+        }
+        let serialized = track.inner.serialize_inline()?;
+        assert!(serialized.contains("\r\n  NAME \"2\"\r\n"));
+        assert_eq!(serialized.matches("\r\n  NAME ").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_template() -> Result<()> {
+        let tracks = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks();
+        let template = tracks[..2]
+            .iter()
+            .map(Track::to_track_template_string)
+            .collect::<Result<String>>()?;
+        assert_eq!(Track::from_track_template_str(&template)?, tracks[..2]);
+
+        let weird_track = Track::from_track_template_str(WEIRD_TRACK_2)?;
+        assert_eq!(
+            weird_track[0].to_track_template_string()?,
+            format!("{WEIRD_TRACK_2}\r\n")
+        );
+
+        assert!(matches!(
+            Track::from_track_template_str("<ITEM\r\n>\r\n"),
+            Err(error::Error::InvalidObject { .. })
+        ));
+        assert!(matches!(
+            Track::from_track_template_str("RIPPLE 0\r\n"),
+            Err(error::Error::InvalidObject { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pan_law() -> Result<()> {
+        let mut track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        assert_eq!(track.pan_law_flags()?, Some(3));
+        assert_eq!(track.pan_law()?, None);
+
+        track.set_pan_law(Some(0.5));
+        track.set_pan_law_flags(Some(1));
+        assert_eq!(track.pan_law()?, Some(0.5));
+        assert_eq!(track.pan_law_flags()?, Some(1));
+        let serialized = track.inner.serialize_inline()?;
+        assert!(serialized.contains("\r\n  PANLAWFLAGS 1\r\n"));
+        assert!(serialized.contains("\r\n  PANLAW 0.5\r\n  <FXCHAIN\r\n"));
+
+        track.set_pan_law(None);
+        track.set_pan_law_flags(None);
+        assert_eq!((track.pan_law()?, track.pan_law_flags()?), (None, None));
+        assert!(!track.inner.serialize_inline()?.contains("PANLAW"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_lanes() -> Result<()> {
+        let track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        let fixed_lanes = track.fixed_lanes()?.expect("fixture has FIXEDLANES");
+        assert_eq!(
+            fixed_lanes,
+            FixedLanes {
+                flags: 9,
+                extra: vec![0, 0, 0, 0]
+            }
+        );
+        assert!(fixed_lanes.enabled());
+        assert!(track.has_spacer()?);
+        // REAPER 7 lines come back as they were
+        assert_eq!(
+            track.to_track_template_string()?,
+            format!("{WEIRD_TRACK_2}\r\n")
+        );
+
+        let mut without_spacer = track.clone();
+        without_spacer.set_spacer(false);
+        assert!(!without_spacer.has_spacer()?);
+        assert_eq!(without_spacer.inner.attributes("SPACER"), None);
+        without_spacer.set_spacer(true);
+        assert!(without_spacer.has_spacer()?);
+        let mut track = track;
+        track.set_spacer(true);
+        assert_eq!(
+            track.to_track_template_string()?,
+            format!("{WEIRD_TRACK_2}\r\n")
+        );
+
+        let track = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks().remove(0);
+        assert_eq!(track.fixed_lanes()?, None);
+        assert!(!track.has_spacer()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_arm() -> Result<()> {
+        let mut track = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks().remove(1);
+        assert!(track.record_arm()?);
+        assert_eq!(track.record_input()?, 1);
+        assert_eq!(track.record_monitoring()?, RecordMonitoring::On);
+
+        track.set_record_arm(false)?;
+        track.set_record_monitoring(RecordMonitoring::NotWhenPlaying)?;
+        assert!(!track.record_arm()?);
+        assert_eq!(track.record_monitoring()?, RecordMonitoring::NotWhenPlaying);
+        assert!(track
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  REC 0 1 2 0 0 0 0 0\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_arm_keeps_unknown_fields() -> Result<()> {
+        let mut track = Track::from_object(low_level::from_str(
+            "<TRACK {C7D7917F-D94F-ED85-1D58-2F258596E414}\r\n  REC 0 4096 7 0 0 0 0 0 1 \"future\" 0.5\r\n>",
+        )?)?;
+        assert!(!track.record_arm()?);
+        assert_eq!(track.record_monitoring()?, RecordMonitoring::Other(7));
+
+        track.set_record_arm(true)?;
+        assert_eq!(
+            track.inner.serialize_inline()?,
+            "<TRACK {C7D7917F-D94F-ED85-1D58-2F258596E414}\r\n  REC 1 4096 7 0 0 0 0 0 1 \"future\" 0.5\r\n>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_metering_config() -> Result<()> {
+        let mut track = Track::from_object(low_level::from_str(WEIRD_TRACK)?)?;
+        assert_eq!(track.metering_config()?, 2);
+        assert_eq!(track.metering_mode()?, MeteringMode::MultichannelPeaks);
+
+        let mut weird_track = Track::from_object(low_level::from_str(WEIRD_TRACK_2)?)?;
+        assert_eq!(weird_track.metering_config()?, 16);
+        assert_eq!(weird_track.metering_mode()?, MeteringMode::LufsShortTermMax);
+
+        track.set_metering_config(MeteringMode::LufsShortTermMax)?;
+        weird_track.set_metering_config(MeteringMode::MultichannelPeaks)?;
+        assert_eq!(track.metering_config()?, 16);
+        assert_eq!(
+            weird_track.metering_mode()?,
+            MeteringMode::MultichannelPeaks
+        );
+        assert!(weird_track
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  VU 2\r\n"));
+        assert_eq!(MeteringMode::from(33), MeteringMode::Other(33));
+        assert_eq!(i64::from(MeteringMode::Other(33)), 33);
+
+        // a track without `VU` gets one
+        let mut bare = Track::from_object(low_level::from_str("<TRACK\r\n  NAME a\r\n>")?)?;
+        assert!(bare.metering_config().is_err());
+        bare.set_metering_config(MeteringMode::StereoRms)?;
+        assert_eq!(bare.metering_mode()?, MeteringMode::StereoRms);
+        Ok(())
+    }
+
+    #[test]
+    fn test_modify_tracks_keeps_other_entries_in_place() -> Result<()> {
+        let mut reaper_project = ReaperProject::parse_from_str(
+            "<REAPER_PROJECT 0.1\r\n  A 1\r\n  <TRACK {C7D7917F-D94F-ED85-1D58-2F258596E414}\r\n    NAME first\r\n  >\r\n  B 2\r\n  <TRACK {F53A9A4F-56D3-4B71-9355-F2E45B8B8E4B}\r\n    NAME second\r\n  >\r\n  <EXTENSIONS\r\n  >\r\n  C 3\r\n>\r\n",
+        )?;
+        reaper_project.modify_tracks(|tracks| tracks.into_iter().rev().collect())?;
+        assert_eq!(
+            reaper_project.serialize_to_string()?,
+            "<REAPER_PROJECT 0.1\r\n  A 1\r\n  <TRACK {F53A9A4F-56D3-4B71-9355-F2E45B8B8E4B}\r\n    NAME second\r\n  >\r\n  <TRACK {C7D7917F-D94F-ED85-1D58-2F258596E414}\r\n    NAME first\r\n  >\r\n  B 2\r\n  <EXTENSIONS\r\n  >\r\n  C 3\r\n>\r\n"
+        );
+
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        reaper_project.modify_tracks(|tracks| tracks)?;
+        assert_eq!(reaper_project.serialize_to_string()?, EXAMPLE_1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extension_state() -> Result<()> {
+        let example = include_str!("../test_data/extension-state.rpp");
+        let reaper_project = ReaperProject::parse_from_str(example)?;
+        let settings = reaper_project
+            .extension_state("MyScript_Settings")
+            .expect("fixture has script state");
+        assert_eq!(
+            settings
+                .iter()
+                .map(|entry| entry.serialize_inline())
+                .collect::<low_level::error::Result<Vec<_>>>()?,
+            [
+                "windowPos 120 80",
+                "enabled 1",
+                "last_file=C:\\Users\\me\\take 1.wav"
+            ]
+        );
+        assert!(reaper_project.extension_state("sws_marker_list").is_some());
+        assert!(reaper_project.extension_state("SWSAUTOCOLOR").is_none());
+        assert!(reaper_project.extension_state("missing").is_none());
+        assert_eq!(reaper_project.serialize_to_string()?, example);
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_items() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let expected = reaper_project
+            .tracks()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, track)| track.items().into_iter().map(move |item| (index, item)))
+            .collect::<Vec<_>>();
+        assert!(expected.len() > 1);
+        assert_eq!(reaper_project.all_items().collect::<Vec<_>>(), expected);
+
+        let mut timeline = reaper_project
+            .all_items()
+            .map(|(track, item)| item.position().map(|position| (position, track)))
+            .collect::<Result<Vec<_>>>()?;
+        timeline.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        assert!(timeline.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_rate_and_length() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        assert_eq!(reaper_project.sample_rate()?, 48000);
+        // `POSITION 0`, `LENGTH 188.04` on the last track
+        assert_eq!(reaper_project.length(), 188.04);
+
+        // the item ending last comes first in the file, the one with no length is skipped
+        let shuffled = ReaperProject::parse_from_str(
+            "<REAPER_PROJECT 0.1 \"6.80/linux-x86_64\" 1690000000\r\n  <TRACK\r\n    <ITEM\r\n      POSITION 10\r\n      LENGTH 30.5\r\n    >\r\n  >\r\n  <TRACK\r\n    <ITEM\r\n      POSITION 0\r\n      LENGTH 5\r\n    >\r\n    <ITEM\r\n      POSITION 20\r\n      LENGTH 3\r\n    >\r\n    <ITEM\r\n      POSITION 100\r\n    >\r\n  >\r\n>",
+        )?;
+        assert_eq!(shuffled.length(), 40.5);
+
+        let empty = ReaperProject::parse_from_str(
+            "<REAPER_PROJECT 0.1 \"6.80/linux-x86_64\" 1690000000\r\n  SAMPLERATE -1 0 0\r\n>",
+        )?;
+        assert_eq!(empty.length(), 0.);
+        assert!(matches!(
+            empty.sample_rate(),
+            Err(error::Error::OutOfRange {
+                field: "SAMPLERATE",
+                value: -1
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_items_sorted_by_position() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let sorted = reaper_project.items_sorted_by_position();
+        assert_eq!(sorted.len(), reaper_project.all_items().count());
+        let positions = sorted
+            .iter()
+            .map(|(_, item)| item.position())
+            .collect::<Result<Vec<_>>>()?;
+        assert!(positions.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(sorted
+            .iter()
+            .all(|(track, item)| track.items().contains(item)));
+
+        // ties keep file order
+        let tracks = reaper_project.tracks();
+        let first = &tracks[0].items()[0];
+        let mut late = first.clone();
+        late.inner
+            .attributes_mut("POSITION")
+            .expect("items have a position")[0] = Attribute::Float(OrderedFloat(1e6));
+        let mut unreadable = first.clone();
+        unreadable
+            .inner
+            .attributes_mut("POSITION")
+            .expect("items have a position")[0] =
+            Attribute::String(ReaperString::Unquoted("later".to_owned()));
+        let mut items = vec![
+            late.clone(),
+            first.clone(),
+            unreadable.clone(),
+            first.clone(),
+        ];
+        items[3].inner.header.values.push(Attribute::Int(Int(1)));
+        let expected = [unreadable, first.clone(), items[3].clone(), late];
+        items.sort_by(cmp_by_position);
+        assert_eq!(items, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tracks_enumerated() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = reaper_project.tracks();
+        let enumerated = reaper_project.tracks_enumerated().collect::<Vec<_>>();
+        assert_eq!(
+            enumerated
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            (0..tracks.len()).collect::<Vec<_>>()
+        );
+        assert!(enumerated
+            .into_iter()
+            .zip(tracks)
+            .all(|((_, enumerated), track)| enumerated == track));
+        assert_eq!(
+            reaper_project
+                .tracks_enumerated()
+                .nth(2)
+                .map(|(index, track)| track.name().map(|name| (index, name)))
+                .transpose()?,
+            Some((2, "3 BASS".to_owned()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_refs() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        assert!(reaper_project
+            .track_refs()
+            .eq(reaper_project.tracks().iter()));
+        assert!(reaper_project
+            .track_refs()
+            .all(|track| track.item_refs().eq(track.items().iter())));
+        let track = reaper_project.track_refs().nth(2).expect("track 3");
+        assert_eq!(track.name()?, "3 BASS");
+        assert!(std::ptr::eq(Track::from_object_ref(track.as_ref())?, track));
+        assert!(Track::from_object_ref(reaper_project.as_ref()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_names() -> Result<()> {
+        let names = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .iter()
+            .take(3)
+            .map(Track::name)
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(names, ["REŻYSERKA MIKROFON", "2", "3 BASS"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_modify_track_by_id() -> Result<()> {
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = reaper_project.tracks();
+        let id = tracks[2]
+            .track_id()
+            .cloned()
+            .expect("fixture tracks have ids");
+        let len = reaper_project.modify_track_by_id(&id, |track| {
+            track.set_name("renamed");
+            track.items().len()
+        })?;
+        assert_eq!(len, tracks[2].items().len());
+        let modified = reaper_project.tracks();
+        assert_eq!(modified[2].name()?, "renamed");
+        assert_eq!(modified[..2], tracks[..2]);
+        assert_eq!(modified[3..], tracks[3..]);
+
+        assert!(matches!(
+            reaper_project.modify_track_by_id(&ReaperUid::random(), |_| ()),
+            Err(error::Error::NoSuchTrack { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_opt() -> Result<()> {
+        let track = |text: &str| {
+            low_level::from_str(text)
+                .map_err(Into::into)
+                .and_then(Track::from_object)
+        };
+        assert_eq!(
+            track("<TRACK\r\n  NAME \"a b\"\r\n>")?
+                .name_opt()
+                .as_deref(),
+            Some("a b")
+        );
+        assert_eq!(track("<TRACK\r\n  NAME \"\"\r\n>")?.name_opt(), None);
+        assert_eq!(track("<TRACK\r\n  NAME \"\"\r\n>")?.name()?, "");
+        assert_eq!(track("<TRACK\r\n  PEAKCOL 1\r\n>")?.name_opt(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_by_name() -> Result<()> {
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = reaper_project.tracks();
+        // stored unquoted as `NAME 2`
+        assert_eq!(reaper_project.track_by_name("2").as_ref(), tracks.get(1));
+        assert_eq!(
+            reaper_project.track_by_name("3 BASS").as_ref(),
+            tracks.get(2)
+        );
+        assert_eq!(reaper_project.track_index_by_name("3 BASS"), Some(2));
+        assert_eq!(reaper_project.track_by_name("\"3 BASS\""), None);
+        assert_eq!(reaper_project.track_index_by_name("missing"), None);
+
+        let mut duplicate = tracks[2].duplicate();
+        duplicate.set_name("2");
+        reaper_project.modify_tracks(|mut tracks| {
+            tracks.push(duplicate.clone());
+            tracks
+        })?;
+        assert_eq!(
+            reaper_project.tracks_by_name("2"),
+            [tracks[1].clone(), duplicate]
+        );
+
+        assert_eq!(
+            reaper_project.modify_track_by_name("2", |track| track.set_name("renamed")),
+            Some(())
+        );
+        assert_eq!(reaper_project.track_index_by_name("renamed"), Some(1));
+        assert_eq!(reaper_project.tracks_by_name("2").len(), 1);
+        assert_eq!(reaper_project.modify_track_by_name("missing", |_| ()), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_lenient() -> Result<()> {
+        let (project, warnings) = ReaperProject::parse_lenient(EXAMPLE_1);
+        assert!(warnings.is_empty());
+        assert_eq!(project, Some(ReaperProject::parse_from_str(EXAMPLE_1)?));
+
+        let damaged = EXAMPLE_1.replacen("    NAME 2\r\n", "    NAME 2 \r\n    NA-ME 2\r\n", 1);
+        assert!(ReaperProject::parse_from_str(&damaged).is_err());
+        let (project, warnings) = ReaperProject::parse_lenient(&damaged);
+        let project = project.expect("only one line is broken");
+        assert_eq!(project.serialize_to_string()?, EXAMPLE_1);
+        assert_eq!(warnings.len(), 2);
+
+        let (project, warnings) = ReaperProject::parse_lenient("<TRACK\n>\n");
+        assert_eq!(project, None);
+        assert!(matches!(warnings[..], [ParseWarning::Unparsable { .. }]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_and_from_str() -> Result<()> {
+        let reaper_project = EXAMPLE_1.parse::<ReaperProject>()?;
+        assert_eq!(reaper_project.to_string(), EXAMPLE_1);
+        let unix = low_level::normalize_line_endings(EXAMPLE_1, LineEnding::Lf);
+        assert_eq!(unix.parse::<ReaperProject>()?.to_string(), unix);
+
+        let track = reaper_project.tracks()[2].clone();
+        assert_eq!(track.to_string(), track.to_track_template_string()?);
+        assert_eq!(track.to_string().parse::<Track>()?, track);
+        let item = track.items()[0].clone();
+        assert_eq!(item.to_string().parse::<Item>()?, item);
+        assert!(matches!(
+            item.to_string().parse::<Track>(),
+            Err(error::Error::InvalidObject { .. })
+        ));
+
+        // not serializable, so shown like Debug
+        let mut broken = item.clone();
+        broken.inner.values.push(Entry::Line(Line {
+            attribute: AttributeName::new(String::new()),
+            values: vec![],
+        }));
+        assert_eq!(broken.to_string(), format!("{broken:?}"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_fx_from() -> Result<()> {
+        let tracks = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks();
+        let fx_ids = |track: &Track| {
+            track
+                .fx_chain()
+                .map(|chain| {
+                    chain
+                        .inner
+                        .values
+                        .iter()
+                        .filter_map(|entry| entry.as_line())
+                        .filter(|line| line.attribute.as_ref() == "FXID")
+                        .map(|line| line.values.clone())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let source = tracks
+            .iter()
+            .find(|track| !fx_ids(track).is_empty())
+            .expect("a track with plugins");
+        let plugins = fx_ids(source).len();
+
+        // no chain at all
+        let mut target = tracks[0].clone();
+        target.inner.remove_entries(|entry| {
+            entry
+                .as_object()
+                .is_some_and(|o| matches_attribute_name_ref(o, FxChain::ATTRIBUTE_NAME))
+        });
+        target.copy_fx_from(source)?;
+        target.copy_fx_from(source)?;
+        let copied = fx_ids(&target);
+        assert_eq!(copied.len(), plugins * 2);
+        assert!(copied.iter().all(|fx_id| !fx_ids(source).contains(fx_id)));
+        assert_ne!(copied[0], copied[plugins]);
+        let chain = target.fx_chain().expect("created above");
+        assert_eq!(
+            chain
+                .inner
+                .serialize_inline()?
+                .lines()
+                .take(4)
+                .collect::<Vec<_>>(),
+            ["<FXCHAIN", "  SHOW 0", "  LASTSEL 0", "  DOCKED 0"]
+        );
+        // plugins come with everything that belongs to them, chain settings stay behind
+        let source_chain = source.fx_chain().expect("checked above").inner;
+        let settings = source_chain
+            .values
+            .iter()
+            .position(|entry| {
+                entry
+                    .as_line()
+                    .is_some_and(|line| line.attribute.as_ref() == "BYPASS")
+            })
+            .expect("a chain with plugins");
+        assert_eq!(
+            chain.inner.values.len(),
+            3 + (source_chain.values.len() - settings) * 2
+        );
+
+        // an existing, empty chain is filled in place
+        let mut target = tracks
+            .iter()
+            .find(|track| {
+                track
+                    .fx_chain()
+                    .is_some_and(|chain| chain.inner.values.len() == 3)
+            })
+            .expect("a track with an empty chain")
+            .clone();
+        target.copy_fx_from(source)?;
+        assert_eq!(fx_ids(&target).len(), plugins);
+        assert_eq!(
+            target
+                .inner
+                .values
+                .iter()
+                .filter(|entry| entry
+                    .as_object()
+                    .is_some_and(|o| matches_attribute_name_ref(o, FxChain::ATTRIBUTE_NAME)))
+                .count(),
+            1
+        );
+
+        // nothing to copy
+        let before = target.clone();
+        target.copy_fx_from(&tracks[0])?;
+        assert_eq!(target, before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_ending_is_preserved() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        assert_eq!(reaper_project.line_ending(), LineEnding::CrLf);
+        assert_eq!(reaper_project.serialize_to_string()?, EXAMPLE_1);
+
+        let unix = low_level::normalize_line_endings(EXAMPLE_1, LineEnding::Lf);
+        let reaper_project = ReaperProject::parse_from_str(&unix)?;
+        assert_eq!(reaper_project.line_ending(), LineEnding::Lf);
+        assert_eq!(reaper_project.serialize_to_string()?, unix);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reader_and_writer_round_trip() -> Result<()> {
+        let unix = low_level::normalize_line_endings(EXAMPLE_1, LineEnding::Lf);
+        for input in [EXAMPLE_1, unix.as_str()] {
+            let reaper_project = ReaperProject::from_reader(input.as_bytes())?;
+            let mut written = Vec::new();
+            reaper_project.clone().serialize_to_writer(&mut written)?;
+            assert_eq!(String::from_utf8(written).unwrap(), input);
+            assert_eq!(reaper_project.serialize_to_string()?, input);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_track_regenerates_uids() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let uids = |track: &Track| {
+            let mut uids = vec![];
+            track
+                .clone()
+                .inner
+                .for_each_attribute_mut(&mut |attribute| {
+                    if let Attribute::ReaperUid(uid) = attribute {
+                        uids.push(uid.clone());
+                    }
+                });
+            uids
+        };
+        let original = reaper_project.tracks().remove(0);
+        let duplicate = original.duplicate();
+        let (original_uids, duplicate_uids) = (uids(&original), uids(&duplicate));
+
+        assert_eq!(original_uids.len(), duplicate_uids.len());
+        assert!(original_uids
+            .iter()
+            .all(|uid| !duplicate_uids.contains(uid)));
+        assert_eq!(
+            duplicate
+                .inner
+                .header
+                .values
+                .first()
+                .and_then(|v| v.as_reaper_uid()),
+            duplicate
+                .inner
+                .single_attribute("TRACKID")
+                .and_then(|v| v.as_reaper_uid())
+        );
+        assert_eq!(duplicate.name()?, original.name()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_version() -> Result<()> {
+        let version = ReaperProject::parse_from_str(EXAMPLE_1)?.version()?;
+        assert_eq!(
+            version,
+            ProjectVersion {
+                format: 0.1,
+                reaper_version: "6.80".to_owned(),
+                platform: Some("linux-x86_64".to_owned()),
+                saved_at: 1691227194,
+            }
+        );
+        assert_eq!(version.reaper_version_number(), Some(6.8));
+
+        let old = ReaperProject::parse_from_str("<REAPER_PROJECT 0.1 \"7.0rc1\" 1\r\n>")?;
+        let version = old.version()?;
+        assert_eq!(version.platform, None);
+        assert_eq!(version.reaper_version_number(), Some(7.));
+
+        let broken = ReaperProject::parse_from_str("<REAPER_PROJECT 0.1 6.80\r\n>")?;
+        assert!(matches!(
+            broken.version(),
+            Err(error::Error::InvalidAttributeType { .. })
+        ));
+        let truncated = ReaperProject::parse_from_str("<REAPER_PROJECT 0.1 \"6.80/win64\"\r\n>")?;
+        assert!(matches!(
+            truncated.version(),
+            Err(error::Error::MissingAttribute { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_touch() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let saved_at = reaper_project.version()?.saved_at;
+        assert_eq!(reaper_project.clone().serialize_to_string()?, EXAMPLE_1);
+
+        let touched = reaper_project
+            .clone()
+            .with_touch_on_save(true)
+            .serialize_to_string()?
+            .pipe(|text| ReaperProject::parse_from_str(&text))?
+            .version()?
+            .saved_at;
+        assert!(touched > saved_at);
+
+        let mut reaper_project = reaper_project;
+        reaper_project.touch()?;
+        assert!(reaper_project.version()?.saved_at >= touched);
+        let mut headless = ReaperProject::parse_from_str("<REAPER_PROJECT 0.1\r\n>")?;
+        assert!(matches!(
+            headless.touch(),
+            Err(error::Error::MissingAttribute { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_cfg() -> Result<()> {
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let mut render_cfg = reaper_project
+            .render_cfg()
+            .expect("fixture has a render config");
+        assert_eq!(&render_cfg.magic()?, b"evaw");
+        assert_eq!(render_cfg.format()?, RenderFormat::Wav);
+
+        render_cfg.set_data(b"l3pm\x00\x01\x02");
+        reaper_project.set_render_cfg(render_cfg);
+        let serialized = reaper_project.serialize_to_string()?;
+        assert_eq!(
+            serialized,
+            EXAMPLE_1.replace("\n    ZXZhdxgAAQ==\r\n", "\n    bDNwbQABAg==\r\n")
+        );
+        let render_cfg = ReaperProject::parse_from_str(&serialized)?
+            .render_cfg()
+            .expect("render config was set");
+        assert_eq!(render_cfg.format()?, RenderFormat::Mp3);
+        assert_eq!(render_cfg.data()?, b"l3pm\x00\x01\x02");
+
+        let mut empty = RenderCfg::from_object(low_level::from_str("<RENDER_CFG\r\n>")?)?;
+        assert!(matches!(
+            empty.format(),
+            Err(error::Error::ChunkTooShort {
+                expected: 4,
+                found: 0
+            })
+        ));
+        empty.set_data(b"ffmp");
+        assert_eq!(empty.format()?, RenderFormat::Ffmpeg);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_stats() -> Result<()> {
+        let stats = ReaperProject::parse_from_str(EXAMPLE_1)?.parse_stats();
+        assert_eq!(
+            stats.objects,
+            EXAMPLE_1
+                .lines()
+                .filter(|line| line.trim_start().starts_with('<'))
+                .count()
+        );
+        assert_eq!(
+            stats.lines + stats.anonymous_parameters + stats.objects * 2,
+            EXAMPLE_1.lines().count()
+        );
+        // only bare words like track and item names are unquoted
+        let strings = stats.attributes[&AttributeKind::String];
+        assert!(stats.unquoted_strings * 2 < strings);
+        Ok(())
+    }
+
+    #[test]
+    fn test_master_track() -> Result<()> {
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let master_track = reaper_project.master_track()?;
+        assert_eq!(master_track.volume, 1.);
+        assert_eq!(master_track.pan, 0.);
+        assert_eq!(master_track.channel_count, 2);
+        assert_eq!(master_track.fx_chain, None);
+
+        reaper_project.set_master_volume(0.5)?;
+        assert_eq!(reaper_project.master_track()?.volume, 0.5);
+        assert!(reaper_project
+            .serialize_to_string()?
+            .contains("\r\n  MASTER_VOLUME 0.5 0 -1 -1 1\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_master_fx_chain() -> Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(
+            "<REAPER_PROJECT 0.1 \"6.80/linux-x86_64\" 1691227194\r\n  MASTER_NCH 2 2\r\n  MASTER_VOLUME 0.25 -0.5 -1 -1 1\r\n  <MASTERFXLIST\r\n    SHOW 0\r\n  >\r\n>",
+        )?;
+        let master_track = reaper_project.master_track()?;
+        assert_eq!((master_track.volume, master_track.pan), (0.25, -0.5));
+        assert!(master_track.fx_chain.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_wave_file() -> Result<()> {
+        let mut item = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .remove(0)
+            .items()
+            .remove(0);
+        let source = item.source_wave().ok_or(error::Error::NoSourceFile)?;
+        assert_eq!(
+            source.file().transpose()?,
+            Some("audio-files/01-REŻYSERKA MIKROFON-230805_1118.wav")
+        );
+
+        item.with_source_waves_mut(|source| {
+            if let Some(Ok(file)) = source.file_mut() {
+                *file = "elsewhere.wav".to_owned();
+            }
+        });
+        let source = item.source_wave().ok_or(error::Error::NoSourceFile)?;
+        assert_eq!(source.file().transpose()?, Some("elsewhere.wav"));
+        assert!(item
+            .inner
+            .serialize_inline()?
+            .contains("\r\n    FILE \"elsewhere.wav\"\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fades() -> Result<()> {
+        let mut item = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .remove(0)
+            .items()
+            .remove(0);
+        let mut fade_in = item.fade_in().expect("fixture item has a fade in");
+        assert_eq!(fade_in.shape()?, 1);
+        assert_eq!(fade_in.length()?, 0.01);
+        assert!(fade_in.is_manual()?);
+        assert_eq!(fade_in.direction()?, 0.);
+
+        fade_in.set_manual(false)?;
+        fade_in.set_direction(-0.5)?;
+        assert!(!fade_in.is_manual()?);
+        item.set_fade_in(fade_in);
+        let serialized = item.inner.serialize_inline()?;
+        assert!(serialized.contains("\r\n  FADEIN 1 0.01 0.01 1 0 -0.5 0\r\n"));
+        assert!(serialized.contains("\r\n  FADEOUT 1 0.01 0 1 0 0 0\r\n"));
+
+        let mut fade_in = item.fade_in().expect("set above");
+        fade_in.set_length(0.5)?;
+        assert_eq!(fade_in.effective_length()?, 0.01);
+        fade_in.set_manual(true)?;
+        assert_eq!((fade_in.length()?, fade_in.is_manual()?), (0.01, true));
+        item.set_fade_in(fade_in);
+        assert!(item
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  FADEIN 1 0.01 0 1 0 -0.5 0\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_uniform_fades_keep_trailing_fields() -> Result<()> {
+        let mut items = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .into_iter()
+            .flat_map(|track| track.items())
+            .collect::<Vec<_>>();
+        // a later REAPER version writing one more value
+        let mut longer = items[0].fade_out().expect("fixture item has a fade out");
+        longer.values.push(Attribute::Int(Int(7)));
+        items[0].set_fade_out(longer);
+        for item in items.iter_mut() {
+            let mut fade_out = item.fade_out().expect("fixture items have fade outs");
+            fade_out.set_shape(2)?;
+            fade_out.set_length(0.25)?;
+            item.set_fade_out(fade_out);
+        }
+        assert!(items[0]
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  FADEOUT 2 0.25 0 1 0 0 0 7\r\n"));
+        for item in items.iter().skip(1) {
+            let fade_out = item.fade_out().expect("set above");
+            assert_eq!((fade_out.shape()?, fade_out.length()?), (2, 0.25));
+            assert_eq!(fade_out.values.len(), 7);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_volume_and_playrate() -> Result<()> {
+        let mut item = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .remove(0)
+            .items()
+            .remove(0);
+        assert_eq!(item.volume()?, 1.);
+        assert_eq!(item.playrate()?, PlayRate::default());
+
+        item.set_volume(0.5)?;
+        let playrate = item.playrate()?;
+        item.set_playrate(PlayRate {
+            rate: 1.25,
+            preserve_pitch: true,
+            ..playrate
+        });
+        assert_eq!(item.play_rate()?, 1.25);
+        let serialized = item.inner.serialize_inline()?;
+        assert!(serialized.contains("\r\n  VOLPAN 0.5 0 1 -1\r\n"));
+        assert!(serialized.contains("\r\n  PLAYRATE 1.25 1 0 -1 0 0.0025\r\n"));
+
+        let values = low_level::parse_line("PLAYRATE 1 0 0 -1 0 0.0025")?.values;
+        let playrate = PlayRate::try_from(values.as_slice())?;
+        assert_eq!(
+            playrate,
+            PlayRate {
+                rate: 1.,
+                preserve_pitch: false,
+                pitch_semitones: 0.,
+                pitch_mode: PlayRate::PROJECT_DEFAULT,
+                stretch_mode: 0,
+                extra: vec![Attribute::Float(OrderedFloat(0.0025))],
+            }
+        );
+        let written = Line {
+            attribute: AttributeName::new(PlayRate::FIELD.to_owned()),
+            values: playrate.into(),
+        };
+        assert_eq!(written.serialize_inline()?, "PLAYRATE 1 0 0 -1 0 0.0025");
+        let older = low_level::parse_line("PLAYRATE 0.5 1 -12")?.values;
+        let older = PlayRate::try_from(older.as_slice())?;
+        assert_eq!(
+            (older.pitch_semitones, older.pitch_mode, older.extra),
+            (-12., -1, vec![])
+        );
+        assert!(matches!(
+            PlayRate::try_from(&values[..2]),
+            Err(error::Error::MissingAttribute { .. })
+        ));
+
+        let mut item = Item::from_object(low_level::from_str("<ITEM\r\n  POSITION 0\r\n>")?)?;
+        assert_eq!(item.playrate()?, PlayRate::default());
+        item.set_playrate(PlayRate::default());
+        assert_eq!(
+            item.inner.serialize_inline()?,
+            "<ITEM\r\n  POSITION 0\r\n  PLAYRATE 1 0 0 -1 0 0.0025\r\n>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_exceeds_source() -> Result<()> {
+        let item = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .remove(0)
+            .items()
+            .remove(0);
+        // SOFFS 4.01064583333333 + LENGTH 7.25335416666667 at PLAYRATE 1
+        assert!(!item.exceeds_source(11.3)?);
+        assert!(!item.exceeds_source(60.)?);
+        assert!(item.exceeds_source(11.2)?);
+        assert!(item.exceeds_source(7.3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_mute_and_selection() -> Result<()> {
+        let mut item = Item::from_object(low_level::from_str(
+            "<ITEM\r\n  POSITION 0\r\n  MUTE 0 1\r\n  SEL 1\r\n>",
+        )?)?;
+        assert!(!item.is_muted()?);
+        assert!(item.is_selected()?);
+        item.set_muted(true);
+        item.set_selected(false);
+        assert!(item.is_muted()?);
+        assert!(!item.is_selected()?);
+        assert_eq!(
+            item.inner.serialize_inline()?,
+            "<ITEM\r\n  POSITION 0\r\n  MUTE 1 1\r\n  SEL 0\r\n>"
+        );
+
+        let mut bare = Item::from_object(low_level::from_str("<ITEM\r\n  POSITION 0\r\n>")?)?;
+        assert!(!bare.is_muted()? && !bare.is_selected()?);
+        bare.set_muted(true);
+        assert_eq!(
+            bare.inner.serialize_inline()?,
+            "<ITEM\r\n  POSITION 0\r\n  MUTE 1 0\r\n>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_offsets() -> Result<()> {
+        let mut item = ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .remove(0)
+            .items()
+            .remove(0);
+        assert_eq!(item.snap_offset()?, 0.);
+        assert_eq!(item.source_offset()?, 4.01064583333333);
+        item.set_snap_offset(0.25);
+        item.set_source_offset(5.);
+        assert_eq!(item.snap_offset()?, 0.25);
+        assert_eq!(item.source_offset()?, 5.);
+        assert_eq!(
+            item.inner
+                .values
+                .iter()
+                .filter(|entry| entry
+                    .as_line()
+                    .is_some_and(|line| line.attribute.as_ref() == "SOFFS"))
+                .count(),
+            1
+        );
+
+        let mut bare = Item::from_object(low_level::from_str(
+            "<ITEM\r\n  POSITION 0\r\n  LENGTH 1\r\n  <SOURCE WAVE\r\n    FILE \"a.wav\"\r\n  >\r\n>",
+        )?)?;
+        assert_eq!((bare.snap_offset()?, bare.source_offset()?), (0., 0.));
+        bare.set_source_offset(0.5);
+        assert_eq!(
+            bare.inner.serialize_inline()?,
+            "<ITEM\r\n  POSITION 0\r\n  LENGTH 1\r\n  SOFFS 0.5\r\n  <SOURCE WAVE\r\n    FILE \"a.wav\"\r\n  >\r\n>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_source_consumed_length() -> Result<()> {
+        let item = |play_rate: &str| {
+            low_level::from_str(&format!(
+                "<ITEM\r\n  POSITION 0\r\n  LENGTH 188.04\r\n  SOFFS 0\r\n  PLAYRATE {play_rate} 0 0 -1 0 0.0025\r\n>"
+            ))
+            .map_err(Into::into)
+            .and_then(Item::from_object)
+        };
+        assert_eq!(item("1")?.source_consumed_length()?, 188.04);
+        assert_eq!(item("0.5")?.source_consumed_length()?, 94.02);
+        assert_eq!(item("2")?.source_consumed_length()?, 376.08);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relocate() -> Result<()> {
+        let files = |project: &ReaperProject| {
+            project
+                .tracks()
+                .into_iter()
+                .flat_map(|track| track.items())
+                .filter_map(|item| item.source_wave())
+                .filter_map(|source| source.file().and_then(|file| file.ok()).map(str::to_owned))
+                .collect::<Vec<_>>()
+        };
+        let mut reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let original = files(&reaper_project);
+        assert!(!original.is_empty());
+
+        reaper_project.relocate(Path::new("/music/project"), Path::new("/music"))?;
+        assert_eq!(
+            files(&reaper_project),
+            original
+                .iter()
+                .map(|file| Path::new("project").join(file).display().to_string())
+                .collect::<Vec<_>>()
+        );
+
+        reaper_project.relocate(Path::new("/music"), Path::new("/elsewhere"))?;
+        assert_eq!(
+            files(&reaper_project),
+            original
+                .iter()
+                .map(|file| Path::new("/music/project").join(file).display().to_string())
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_path_separator() -> Result<()> {
+        let files = |project: &ReaperProject| {
+            project
+                .tracks()
+                .into_iter()
+                .flat_map(|track| track.items())
+                .filter_map(|item| item.source_wave())
+                .filter_map(|source| source.file().and_then(|file| file.ok()).map(str::to_owned))
+                .collect::<Vec<_>>()
+        };
+        let mut reaper_project = ReaperProject::parse_from_str(
+            &EXAMPLE_1.replace("FILE \"audio-files/", "FILE \"C:\\Users\\me/audio-files\\"),
+        )?;
+        let original = files(&reaper_project);
+        assert!(original[0].starts_with("C:\\Users\\me/audio-files\\"));
+
+        reaper_project.set_path_separator(PathSeparator::Slash)?;
+        assert_eq!(
+            files(&reaper_project),
+            original
+                .iter()
+                .map(|file| file.replace('\\', "/"))
+                .collect::<Vec<_>>()
+        );
+        assert!(files(&reaper_project)[0].starts_with("C:/Users/me/audio-files/"));
+
+        reaper_project.set_path_separator(PathSeparator::Backslash)?;
+        assert_eq!(
+            files(&reaper_project),
+            original
+                .iter()
+                .map(|file| file.replace('/', "\\"))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_unused_media() -> eyre::Result<()> {
+        let reaper_project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let project_dir = std::env::temp_dir().join(format!(
+            "reaper-save-rs-unused-media-{}",
+            std::process::id()
+        ));
+        let media_dir = project_dir.join("audio-files");
+        let other_dir = project_dir.join("other-files");
+        std::fs::create_dir_all(&media_dir)?;
+        std::fs::create_dir_all(&other_dir)?;
+        let referenced = media_dir.join("01-REŻYSERKA MIKROFON-230805_1118.wav");
+        let different_case = media_dir.join("02-2-230805_1118.WAV");
+        let orphan = media_dir.join("orphan.wav");
+        let same_name_elsewhere = other_dir.join("01-REŻYSERKA MIKROFON-230805_1118.wav");
+        for file in [&referenced, &different_case, &orphan, &same_name_elsewhere] {
+            std::fs::write(file, [])?;
+        }
+
+        let case_insensitive = media_dir.join("02-2-230805_1118.wav").exists();
+
+        // every take and the file wrapped by a `SECTION` source count as used
+        let takes_project = ReaperProject::parse_from_str(
+            "<REAPER_PROJECT 0.1 \"6.80/linux-x86_64\" 1690000000\r\n  <TRACK\r\n    <ITEM\r\n      POSITION 0\r\n      LENGTH 2\r\n      NAME first\r\n      <SOURCE WAVE\r\n        FILE \"takes/first.wav\"\r\n      >\r\n      TAKE SEL\r\n      NAME second\r\n      <SOURCE WAVE\r\n        FILE \"takes\\second.wav\"\r\n      >\r\n    >\r\n    <ITEM\r\n      POSITION 2\r\n      LENGTH 1\r\n      <SOURCE SECTION\r\n        LENGTH 1\r\n        STARTPOS 0.5\r\n        <SOURCE WAVE\r\n          FILE \"takes/section.wav\"\r\n        >\r\n      >\r\n    >\r\n  >\r\n>",
+        )?;
+        let takes_dir = project_dir.join("takes");
+        std::fs::create_dir_all(&takes_dir)?;
+        let take_orphan = takes_dir.join("orphan.wav");
+        for file in ["first.wav", "second.wav", "section.wav", "orphan.wav"] {
+            std::fs::write(takes_dir.join(file), [])?;
+        }
+
+        let unused = reaper_project.find_unused_media(&project_dir, &media_dir);
+        let unused_respelled =
+            reaper_project.find_unused_media(&project_dir, &other_dir.join("../audio-files"));
+        let unused_elsewhere = reaper_project.find_unused_media(&project_dir, &other_dir);
+        let unused_takes = takes_project.find_unused_media(&project_dir, &takes_dir);
+        std::fs::remove_dir_all(&project_dir)?;
+        if case_insensitive {
+            assert_eq!(unused, vec![orphan.clone()]);
+        } else {
+            assert_eq!(unused, vec![different_case, orphan.clone()]);
+        }
+        assert_eq!(unused_respelled.len(), unused.len());
+        assert!(unused_respelled
+            .iter()
+            .any(|path| path.ends_with("orphan.wav")));
+        assert_eq!(unused_elsewhere, vec![same_name_elsewhere]);
+        assert_eq!(unused_takes, vec![take_orphan]);
+        Ok(())
+    }
 }
@@ -0,0 +1,315 @@
+//! automation envelopes. every automatable parameter gets its own object, named after it
+//! (`<VOLENV2>`, `<PANENV2>`, `<PARMENV>`, `<TEMPOENVEX>`...), holding a few settings lines
+//! followed by one `PT <position> <value> <shape> ...` line per point, sorted by position
+use super::*;
+
+const PT: &str = "PT";
+
+/// `<PARAM>ENV`, optionally followed by a number or `EX`
+pub(crate) fn is_envelope_name(name: &str) -> bool {
+    let name = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let name = name.strip_suffix("EX").unwrap_or(name);
+    name.len() > "ENV".len() && name.ends_with("ENV")
+}
+
+fn is_point(entry: &Entry) -> bool {
+    entry
+        .as_line()
+        .is_some_and(|line| line.attribute.as_ref() == PT)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopePoint {
+    /// seconds from the project start
+    pub position: f64,
+    /// in the units of the envelope, e.g. linear gain for volume
+    pub value: f64,
+    /// `0` linear, `1` square, `2` slow start/end, `3` fast start, `4` fast end, `5` bezier
+    pub shape: i64,
+    /// bezier tension, selection and the like, kept as written
+    pub extra: Vec<Attribute>,
+}
+
+impl EnvelopePoint {
+    /// linear point, as REAPER adds it
+    pub fn new(position: f64, value: f64) -> Self {
+        Self {
+            position,
+            value,
+            shape: 0,
+            extra: vec![],
+        }
+    }
+
+    fn from_line(line: &Line) -> Result<Self> {
+        let value = |index: usize| {
+            line.values
+                .get(index)
+                .ok_or_else(|| error::Error::MissingAttribute {
+                    attribute: AttributeName::new(PT.to_owned()),
+                })
+        };
+        Ok(Self {
+            position: value(0).and_then(|v| as_number(PT, v))?,
+            value: value(1).and_then(|v| as_number(PT, v))?,
+            // REAPER leaves the shape out of points using the envelope default
+            shape: line
+                .values
+                .get(2)
+                .map(|v| as_int(PT, v))
+                .transpose()?
+                .unwrap_or(0),
+            extra: line.values.iter().skip(3).cloned().collect(),
+        })
+    }
+
+    fn into_line(self) -> Line {
+        Line {
+            attribute: AttributeName::new(PT.to_owned()),
+            values: [
+                Attribute::Float(OrderedFloat(self.position)),
+                Attribute::Float(OrderedFloat(self.value)),
+                Attribute::Int(Int(self.shape)),
+            ]
+            .into_iter()
+            .chain(self.extra)
+            .collect(),
+        }
+    }
+}
+
+impl Envelope {
+    /// name of the envelope object, like `VOLENV2`
+    pub fn name(&self) -> &str {
+        self.inner.header.attribute.as_ref()
+    }
+
+    /// points of this envelope, in the order they are written
+    pub fn points(&self) -> Result<Vec<EnvelopePoint>> {
+        self.inner
+            .values
+            .iter()
+            .filter(|entry| is_point(entry))
+            .filter_map(|entry| entry.as_line())
+            .map(EnvelopePoint::from_line)
+            .collect()
+    }
+
+    /// inserts `point` after the last point not later than it, keeping the points sorted
+    pub fn add_point(&mut self, point: EnvelopePoint) -> Result<()> {
+        let position = point.position;
+        let entry = Entry::Line(point.into_line());
+        let mut earlier = vec![];
+        for (index, entry) in self.inner.values.iter().enumerate() {
+            if let Some(line) = entry.as_line().filter(|line| line.attribute.as_ref() == PT) {
+                if EnvelopePoint::from_line(line)?.position <= position {
+                    earlier.push(index);
+                }
+            }
+        }
+        let index = match earlier.last() {
+            Some(index) => index + 1,
+            None => self
+                .inner
+                .values
+                .iter()
+                .position(|entry| is_point(entry) || entry.as_object().is_some())
+                .unwrap_or(self.inner.values.len()),
+        };
+        self.inner.values.insert(index, entry);
+        Ok(())
+    }
+
+    /// removes the `index`-th point (as returned by [`Envelope::points`])
+    pub fn remove_point(&mut self, index: usize) -> Result<Option<EnvelopePoint>> {
+        let Some(position) = self
+            .inner
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| is_point(entry))
+            .map(|(position, _)| position)
+            .nth(index)
+        else {
+            return Ok(None);
+        };
+        let removed = self.inner.values.remove(position);
+        removed.as_line().map(EnvelopePoint::from_line).transpose()
+    }
+}
+
+impl Track {
+    /// automation envelopes of the track itself, send envelopes included
+    pub fn envelopes(&self) -> Vec<Envelope> {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Envelope::matches_object(o))
+            .cloned()
+            .map(Envelope::from_object_raw)
+            .collect()
+    }
+    pub fn modify_envelopes<T>(
+        &mut self,
+        mut modify_envelopes: impl FnMut(&mut Envelope) -> T,
+    ) -> Vec<T> {
+        self.inner
+            .values
+            .iter_mut()
+            .filter_map(|e| e.as_object_mut())
+            .filter(|object| Envelope::matches_object(object))
+            .map(|o| Envelope::with_as_object_mut(o, &mut modify_envelopes).expect("checked above"))
+            .collect()
+    }
+}
+
+impl ReaperProject {
+    /// project level envelopes: master track automation, tempo and playrate
+    pub fn envelopes(&self) -> Vec<Envelope> {
+        self.inner
+            .values
+            .iter()
+            .filter_map(|e| e.as_object())
+            .filter(|o| Envelope::matches_object(o))
+            .cloned()
+            .map(Envelope::from_object_raw)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+    const VOLUME_ENVELOPE: &str = r#"<TRACK {EF045EFF-07D6-5EC9-F31B-3EC7C14D9BC8}
+  NAME automated
+  <VOLENV2
+    EGUID {0F0B4B9A-6E3C-4F4E-9D6A-3B1B1E7C2A11}
+    ACT 1 -1
+    VIS 1 1 1
+    LANEHEIGHT 0 0
+    ARM 0
+    DEFSHAPE 0 -1 -1
+    VOLTYPE 1
+    PT 0 1 0
+    PT 2.5 0.5 5 1 0 0.3
+    PT 4 0.25 0 0 1
+  >
+>
+"#;
+
+    fn volume_envelope() -> Result<(Track, Envelope)> {
+        let track = Track::from_track_template_str(VOLUME_ENVELOPE)?.remove(0);
+        let envelope = track.envelopes().remove(0);
+        Ok((track, envelope))
+    }
+
+    #[test]
+    fn test_is_envelope_name() {
+        for name in [
+            "VOLENV",
+            "VOLENV2",
+            "PANENV2",
+            "PARMENV",
+            "AUXVOLENV",
+            "TEMPOENVEX",
+        ] {
+            assert!(is_envelope_name(name), "{name}");
+        }
+        for name in ["ENV", "TRACK", "ENVELOPE", "FXCHAIN", "ITEM"] {
+            assert!(!is_envelope_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_envelope_points() -> Result<()> {
+        let (track, envelope) = volume_envelope()?;
+        assert_eq!(envelope.name(), "VOLENV2");
+        assert_eq!(
+            envelope.points()?,
+            vec![
+                EnvelopePoint::new(0., 1.),
+                EnvelopePoint {
+                    shape: 5,
+                    extra: vec![
+                        Attribute::Int(Int(1)),
+                        Attribute::Int(Int(0)),
+                        Attribute::Float(OrderedFloat(0.3)),
+                    ],
+                    ..EnvelopePoint::new(2.5, 0.5)
+                },
+                EnvelopePoint {
+                    extra: vec![Attribute::Int(Int(0)), Attribute::Int(Int(1))],
+                    ..EnvelopePoint::new(4., 0.25)
+                },
+            ]
+        );
+        assert_eq!(
+            track.to_track_template_string()?,
+            low_level::normalize_line_endings(VOLUME_ENVELOPE, LineEnding::CrLf)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_remove_points() -> Result<()> {
+        let (_, mut envelope) = volume_envelope()?;
+        let original = envelope.clone();
+        envelope.add_point(EnvelopePoint::new(3., 0.75))?;
+        envelope.add_point(EnvelopePoint::new(10., 0.))?;
+        assert_eq!(
+            envelope
+                .points()?
+                .iter()
+                .map(|point| point.position)
+                .collect::<Vec<_>>(),
+            [0., 2.5, 3., 4., 10.]
+        );
+        assert_eq!(
+            envelope.remove_point(2)?,
+            Some(EnvelopePoint::new(3., 0.75))
+        );
+        assert_eq!(envelope.remove_point(3)?, Some(EnvelopePoint::new(10., 0.)));
+        assert_eq!(envelope.remove_point(3)?, None);
+        assert_eq!(envelope, original);
+
+        envelope.remove_point(0)?;
+        envelope.remove_point(0)?;
+        envelope.remove_point(0)?;
+        envelope.add_point(EnvelopePoint::new(1., 1.))?;
+        assert_eq!(
+            envelope
+                .inner
+                .values
+                .last()
+                .and_then(|entry| entry.as_line()),
+            Some(&EnvelopePoint::new(1., 1.).into_line())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_modify_envelopes() -> Result<()> {
+        let (mut track, _) = volume_envelope()?;
+        track.modify_envelopes(|envelope| envelope.remove_point(0));
+        assert_eq!(track.envelopes()[0].points()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_envelopes() -> Result<()> {
+        let project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        assert_eq!(
+            project
+                .envelopes()
+                .iter()
+                .map(Envelope::name)
+                .collect::<Vec<_>>(),
+            ["MASTERPLAYSPEEDENV", "TEMPOENVEX"]
+        );
+        Ok(())
+    }
+}
@@ -18,13 +18,21 @@ pub enum Error {
     },
     #[error("Expected attribute {attribute:?} is missing.")]
     MissingAttribute { attribute: AttributeName },
-    #[error("Invalid attribute kind for [{field}]: expected [{expected:?}], found [{found:?}]")]
+    #[error("Invalid attribute kind for [{field}]: expected [{expected}], found [{found}]")]
     InvalidAttributeType {
         field: &'static str,
         expected: AttributeKind,
         found: AttributeKind,
     },
-    #[error("Ttem has source wave")]
+    #[error("No track with uid {uid:?}")]
+    NoSuchTrack { uid: ReaperUid },
+    #[error("Chunk is {found} bytes long, expected at least {expected}")]
+    ChunkTooShort { expected: usize, found: usize },
+    #[error("Item has no source wave")]
     NoSourceFile,
+    #[error("There is no track group {group}, groups are numbered 1 to 64")]
+    InvalidGroup { group: u32 },
+    #[error("Value {value} of [{field}] is out of range")]
+    OutOfRange { field: &'static str, value: i64 },
 }
 pub type Result<T> = std::result::Result<T, self::Error>;
@@ -0,0 +1,388 @@
+//! folder hierarchy of tracks. the project stores tracks as a flat list and each track says
+//! how it changes the folder depth in `ISBUS <folder> <depth change>`: `ISBUS 1 1` opens a
+//! folder holding the tracks after it, `ISBUS 2 -1` ends the innermost folder after this track
+//! (`-2` ends two, and so on)
+use super::*;
+
+/// a track with the tracks inside it, if it is a folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackNode {
+    pub track: Track,
+    pub children: Vec<TrackNode>,
+}
+
+impl Track {
+    /// second value of `ISBUS`, missing means `0`
+    pub fn folder_depth_change(&self) -> Result<i64> {
+        int_at(&self.inner, "ISBUS", 1).unwrap_or(Ok(0))
+    }
+    pub fn is_folder(&self) -> Result<bool> {
+        self.folder_depth_change().map(|change| change > 0)
+    }
+    /// writes `ISBUS` for a track changing the folder depth by `change`, marking it as a
+    /// folder (`1`), the last track of a folder (`2`) or neither (`0`) to match
+    pub fn set_folder_depth_change(&mut self, change: i64) {
+        let folder = match change {
+            1.. => 1,
+            0 => 0,
+            _ => 2,
+        };
+        set_line(
+            &mut self.inner,
+            "ISBUS",
+            Some(vec![
+                Attribute::Int(Int(folder)),
+                Attribute::Int(Int(change)),
+            ]),
+        )
+    }
+}
+
+/// folder depth changes for the `included` subset of `tracks`, in order. a folder stays a
+/// folder only when all of its tracks are included, otherwise it is flattened, so an extracted
+/// track never ends up inside a folder it wasn't in together with all its siblings
+pub(super) fn subset_depth_changes(
+    tracks: &[Track],
+    included: &std::collections::BTreeSet<usize>,
+) -> Result<Vec<i64>> {
+    let mut open: Vec<usize> = vec![];
+    let mut ancestors = Vec::with_capacity(tracks.len());
+    for (index, track) in tracks.iter().enumerate() {
+        ancestors.push(open.clone());
+        match track.folder_depth_change()? {
+            1.. => open.push(index),
+            0 => {}
+            change => open.truncate(open.len().saturating_sub(change.unsigned_abs() as usize)),
+        }
+    }
+    let is_kept_folder = |folder: usize| {
+        let mut members = ancestors
+            .iter()
+            .enumerate()
+            .filter(|(_, ancestors)| ancestors.contains(&folder))
+            .map(|(index, _)| index)
+            .peekable();
+        members.peek().is_some() && members.all(|member| included.contains(&member))
+    };
+    let depths = included
+        .iter()
+        .map(|index| {
+            ancestors[*index]
+                .iter()
+                .filter(|folder| included.contains(folder) && is_kept_folder(**folder))
+                .count() as i64
+        })
+        .collect::<Vec<_>>();
+    Ok(depths
+        .iter()
+        .zip(depths.iter().skip(1).chain([&0]))
+        .map(|(depth, next)| next - depth)
+        .collect())
+}
+
+impl ReaperProject {
+    /// removes the track whose `TRACKID` is `track_id` and returns it. the folder structure of
+    /// the remaining tracks is kept: children of a removed folder move up a level, and when the
+    /// removed track closed folders, the track before it closes them instead. receives from the
+    /// removed track are dropped and the others renumbered to keep their sources
+    pub fn remove_track(&mut self, track_id: &ReaperUid) -> Result<Track> {
+        let positions = self
+            .inner
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.as_object().is_some_and(Track::matches_object))
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        let track_at = |position: usize| {
+            self.inner.values[position]
+                .as_object()
+                .map(Track::from_object_ref_raw)
+                .expect("positions of tracks")
+        };
+        let index = positions
+            .iter()
+            .position(|position| track_at(*position).track_id() == Some(track_id))
+            .ok_or_else(|| error::Error::NoSuchTrack {
+                uid: track_id.clone(),
+            })?;
+        let change = track_at(positions[index]).folder_depth_change()?;
+
+        // new depth changes for the neighbours, worked out before anything is modified
+        let mut fixes = vec![];
+        match change {
+            ..=-1 => {
+                if let Some(&previous) = index.checked_sub(1).map(|index| &positions[index]) {
+                    fixes.push((previous, track_at(previous).folder_depth_change()? + change));
+                }
+            }
+            0 => {}
+            1.. => {
+                // the removed folder spans depths `1..=change` relative to where it starts,
+                // whichever tracks closed those levels no longer have to
+                let mut depth = change;
+                for &position in &positions[index + 1..] {
+                    let own_change = track_at(position).folder_depth_change()?;
+                    let previous_depth = depth;
+                    depth += own_change;
+                    let closed = previous_depth.min(change) - depth.max(0);
+                    if closed > 0 {
+                        fixes.push((position, own_change + closed));
+                    }
+                    if depth <= 0 {
+                        break;
+                    }
+                }
+            }
+        }
+        for (position, change) in fixes {
+            if let Some(object) = self.inner.values[position].as_object_mut() {
+                Track::with_as_object_mut(object, |track| track.set_folder_depth_change(change))?;
+            }
+        }
+        let removed = self
+            .inner
+            .values
+            .remove(positions[index])
+            .pipe(|entry| match entry {
+                Entry::Object(object) => Track::from_object_raw(object),
+                _ => unreachable!("positions of tracks"),
+            });
+        let index = index as i64;
+        self.try_for_each_track_mut(|track| {
+            track.renumber_receives(|source| match source.cmp(&index) {
+                std::cmp::Ordering::Less => Some(source),
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Greater => Some(source - 1),
+            })
+        })?;
+        Ok(removed)
+    }
+
+    /// top level tracks with their folders nested inside them, in project order. folders
+    /// left open at the end of the project are closed there, closing more folders than are
+    /// open stops at the top level
+    pub fn track_tree(&self) -> Result<Vec<TrackNode>> {
+        fn attach(roots: &mut Vec<TrackNode>, open: &mut [TrackNode], node: TrackNode) {
+            match open.last_mut() {
+                Some(folder) => folder.children.push(node),
+                None => roots.push(node),
+            }
+        }
+        let mut roots = vec![];
+        let mut open: Vec<TrackNode> = vec![];
+        for track in self.tracks() {
+            let change = track.folder_depth_change()?;
+            let node = TrackNode {
+                track,
+                children: vec![],
+            };
+            if change > 0 {
+                open.push(node);
+                continue;
+            }
+            attach(&mut roots, &mut open, node);
+            for _ in 0..change.unsigned_abs() {
+                match open.pop() {
+                    Some(folder) => attach(&mut roots, &mut open, folder),
+                    None => break,
+                }
+            }
+        }
+        while let Some(folder) = open.pop() {
+            attach(&mut roots, &mut open, folder);
+        }
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    fn names(nodes: &[TrackNode]) -> Result<Vec<String>> {
+        nodes.iter().map(|node| node.track.name()).collect()
+    }
+
+    #[test]
+    fn test_track_tree() -> Result<()> {
+        let project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = project.tracks();
+        let tree = project.track_tree()?;
+        // two folders of four tracks each
+        assert_eq!(tree.len(), tracks.len() - 8);
+
+        let folder = &tree[10];
+        assert!(folder.track.is_folder()?);
+        assert_eq!(folder.track, tracks[10]);
+        assert_eq!(
+            names(&folder.children)?,
+            tracks[11..15]
+                .iter()
+                .map(Track::name)
+                .collect::<Result<Vec<_>>>()?
+        );
+        assert!(folder
+            .children
+            .iter()
+            .all(|child| child.children.is_empty()));
+        assert_eq!(tree[11].track, tracks[15]);
+        assert_eq!(tree[17].track, tracks[21]);
+        assert_eq!(tree[17].children.len(), 4);
+        Ok(())
+    }
+
+    fn folder_project(tracks: &[(&str, i64)]) -> Result<ReaperProject> {
+        let tracks = tracks
+            .iter()
+            .enumerate()
+            .map(|(index, (name, change))| {
+                format!(
+                    "  <TRACK\r\n    NAME {name}\r\n    TRACKID {{00000000-0000-0000-0000-{index:012}}}\r\n    ISBUS 0 {change}\r\n  >\r\n"
+                )
+            })
+            .collect::<String>();
+        ReaperProject::parse_from_str(&format!("<REAPER_PROJECT\r\n{tracks}>\r\n"))
+    }
+
+    fn track_id(index: usize) -> ReaperUid {
+        ReaperUid(format!("00000000-0000-0000-0000-{index:012}"))
+    }
+
+    fn depth_changes(project: &ReaperProject) -> Result<Vec<(String, i64)>> {
+        project
+            .tracks()
+            .iter()
+            .map(|track| Ok((track.name()?, track.folder_depth_change()?)))
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_track() -> Result<()> {
+        let tracks = [("a", 0), ("b", 1), ("c", 0), ("d", -1), ("e", 0)];
+        let owned = |tracks: &[(&str, i64)]| {
+            tracks
+                .iter()
+                .map(|(name, change)| (name.to_string(), *change))
+                .collect::<Vec<_>>()
+        };
+
+        let mut project = folder_project(&tracks)?;
+        let removed = project.remove_track(&track_id(0))?;
+        assert_eq!(removed.name()?, "a");
+        assert_eq!(depth_changes(&project)?, owned(&tracks[1..]));
+
+        // removing the folder promotes its children
+        let mut project = folder_project(&tracks)?;
+        project.remove_track(&track_id(1))?;
+        assert_eq!(
+            depth_changes(&project)?,
+            owned(&[("a", 0), ("c", 0), ("d", 0), ("e", 0)])
+        );
+
+        // removing the last child hands the folder end to the one before it
+        let mut project = folder_project(&tracks)?;
+        project.remove_track(&track_id(3))?;
+        assert_eq!(
+            depth_changes(&project)?,
+            owned(&[("a", 0), ("b", 1), ("c", -1), ("e", 0)])
+        );
+        assert_eq!(
+            project.tracks()[2].inner.attributes("ISBUS"),
+            Some(&vec![Attribute::Int(Int(2)), Attribute::Int(Int(-1))])
+        );
+        // and a folder left without children is a plain track
+        project.remove_track(&track_id(2))?;
+        assert_eq!(
+            depth_changes(&project)?,
+            owned(&[("a", 0), ("b", 0), ("e", 0)])
+        );
+
+        assert!(matches!(
+            project.remove_track(&track_id(2)),
+            Err(error::Error::NoSuchTrack { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_track_renumbers_receives() -> Result<()> {
+        let mut project = folder_project(&[("a", 0), ("b", 0), ("c", 0), ("d", 0)])?;
+        project.modify_tracks(|mut tracks| {
+            tracks[0].add_receive(routing::AuxReceive::new(3));
+            [0, 1, 2].into_iter().for_each(|source| {
+                tracks[3].add_receive(routing::AuxReceive::new(source));
+            });
+            tracks
+        })?;
+        project.remove_track(&track_id(1))?;
+        let sources = project
+            .tracks()
+            .iter()
+            .map(|track| {
+                track.receives().map(|receives| {
+                    receives
+                        .into_iter()
+                        .map(|receive| receive.source_track)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(sources, [vec![2], vec![], vec![0, 1]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_nested_folder() -> Result<()> {
+        let tracks = [("a", 1), ("b", 1), ("c", 0), ("d", -2), ("e", 0)];
+        let mut project = folder_project(&tracks)?;
+        project.remove_track(&track_id(1))?;
+        let tree = project.track_tree()?;
+        assert_eq!(names(&tree)?, ["a", "e"]);
+        assert_eq!(names(&tree[0].children)?, ["c", "d"]);
+        assert_eq!(project.tracks()[2].folder_depth_change()?, -1);
+
+        let mut project = folder_project(&tracks)?;
+        project.remove_track(&track_id(0))?;
+        let tree = project.track_tree()?;
+        assert_eq!(names(&tree)?, ["b", "e"]);
+        assert_eq!(names(&tree[0].children)?, ["c", "d"]);
+
+        let mut project = folder_project(&tracks)?;
+        project.remove_track(&track_id(3))?;
+        let tree = project.track_tree()?;
+        assert_eq!(names(&tree)?, ["a", "e"]);
+        assert_eq!(names(&tree[0].children)?, ["b"]);
+        assert_eq!(names(&tree[0].children[0].children)?, ["c"]);
+
+        let mut project = folder_project(&[("a", 1), ("b", 1), ("c", -2)])?;
+        project.remove_track(&track_id(2))?;
+        assert_eq!(project.tracks()[1].folder_depth_change()?, -1);
+        assert_eq!(names(&project.track_tree()?[0].children)?, ["b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_track_tree_unbalanced() -> Result<()> {
+        let track = |name: &str, isbus: &str| {
+            format!("  <TRACK\r\n    NAME {name}\r\n    ISBUS {isbus}\r\n  >\r\n")
+        };
+        let project = ReaperProject::parse_from_str(&format!(
+            "<REAPER_PROJECT\r\n{}{}{}{}{}>\r\n",
+            track("a", "2 -1"),
+            track("b", "1 1"),
+            track("c", "1 1"),
+            track("d", "2 -2"),
+            track("e", "1 1"),
+        ))?;
+        let tree = project.track_tree()?;
+        assert_eq!(names(&tree)?, ["a", "b", "e"]);
+        assert_eq!(names(&tree[1].children)?, ["c"]);
+        assert_eq!(names(&tree[1].children[0].children)?, ["d"]);
+        assert!(tree[2].children.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! track groups. membership is stored as bitmasks, one per grouped parameter and role
+//! (volume lead, volume follow, pan lead...), in `GROUP_FLAGS` for groups 1 to 32 and in
+//! `GROUP_FLAGS_HIGH` for groups 33 to 64. bit `n` of a mask stands for group `n + 1`
+//! (`n + 33` in the high line)
+use super::*;
+
+const GROUP_FLAGS: &str = "GROUP_FLAGS";
+const GROUP_FLAGS_HIGH: &str = "GROUP_FLAGS_HIGH";
+/// highest group number REAPER supports
+pub const MAX_GROUP: u32 = 64;
+
+/// line and bit of `group`
+fn group_bit(group: u32) -> Result<(&'static str, u32)> {
+    match group {
+        1..=32 => Ok((GROUP_FLAGS, 1 << (group - 1))),
+        33..=MAX_GROUP => Ok((GROUP_FLAGS_HIGH, 1 << (group - 33))),
+        _ => Err(error::Error::InvalidGroup { group }),
+    }
+}
+
+impl Track {
+    fn group_masks(&self, field: &'static str) -> Result<Vec<u32>> {
+        self.inner
+            .attributes(field)
+            .into_iter()
+            .flatten()
+            // written unsigned, but take a negative (signed 32 bit) mask as well
+            .map(|value| as_int(field, value).map(|mask| mask as u32))
+            .collect()
+    }
+
+    fn set_group_masks(&mut self, field: &'static str, masks: Vec<u32>) {
+        set_line(
+            &mut self.inner,
+            field,
+            Some(
+                masks
+                    .into_iter()
+                    .map(|mask| Attribute::Int(Int(mask.into())))
+                    .collect(),
+            ),
+        )
+    }
+
+    /// groups the track belongs to in any role, in ascending order
+    pub fn groups(&self) -> Result<Vec<u32>> {
+        let mask = |field| {
+            self.group_masks(field)
+                .map(|masks| masks.into_iter().fold(0, |all, mask| all | mask))
+        };
+        let (low, high) = (mask(GROUP_FLAGS)?, mask(GROUP_FLAGS_HIGH)?);
+        Ok((0..32)
+            .filter(|bit| low & (1 << bit) != 0)
+            .map(|bit| bit + 1)
+            .chain(
+                (0..32)
+                    .filter(|bit| high & (1 << bit) != 0)
+                    .map(|bit| bit + 33),
+            )
+            .collect())
+    }
+
+    /// joins `group` (`1..=64`) as volume lead and follower, the first two masks. other roles
+    /// are left as they are
+    pub fn add_to_group(&mut self, group: u32) -> Result<()> {
+        let (field, bit) = group_bit(group)?;
+        let mut masks = self.group_masks(field)?;
+        if masks.len() < 2 {
+            masks.resize(2, 0);
+        }
+        masks.iter_mut().take(2).for_each(|mask| *mask |= bit);
+        self.set_group_masks(field, masks);
+        Ok(())
+    }
+
+    /// leaves `group` (`1..=64`) in every role
+    pub fn remove_from_group(&mut self, group: u32) -> Result<()> {
+        let (field, bit) = group_bit(group)?;
+        if self.inner.attributes(field).is_some() {
+            let masks = self.group_masks(field)?;
+            self.set_group_masks(field, masks.into_iter().map(|mask| mask & !bit).collect());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const GROUPED_TRACK: &str = "<TRACK\r\n  NAME grouped\r\n  GROUP_FLAGS 0 4 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 8\r\n  GROUP_FLAGS_HIGH 1 0\r\n>\r\n";
+
+    #[test]
+    fn test_groups() -> Result<()> {
+        let mut track = Track::from_track_template_str(GROUPED_TRACK)?.remove(0);
+        assert_eq!(track.groups()?, [3, 4, 33]);
+        track.remove_from_group(3)?;
+        track.remove_from_group(5)?;
+        assert_eq!(track.groups()?, [4, 33]);
+        assert_eq!(
+            track
+                .inner
+                .attributes(GROUP_FLAGS)
+                .map(|masks| masks[1].clone()),
+            Some(Attribute::Int(Int(0)))
+        );
+        assert!(matches!(
+            track.add_to_group(0),
+            Err(error::Error::InvalidGroup { group: 0 })
+        ));
+        assert!(matches!(
+            track.remove_from_group(MAX_GROUP + 1),
+            Err(error::Error::InvalidGroup { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_to_group() -> Result<()> {
+        let mut track =
+            Track::from_track_template_str("<TRACK\r\n  NAME plain\r\n>\r\n")?.remove(0);
+        assert!(track.groups()?.is_empty());
+        for group in [1, 32, 64] {
+            track.add_to_group(group)?;
+        }
+        assert_eq!(track.groups()?, [1, 32, 64]);
+        assert_eq!(
+            track.to_track_template_string()?,
+            "<TRACK\r\n  NAME plain\r\n  GROUP_FLAGS 2147483649 2147483649\r\n  GROUP_FLAGS_HIGH 2147483648 2147483648\r\n>\r\n"
+        );
+        track.remove_from_group(64)?;
+        assert_eq!(track.groups()?, [1, 32]);
+        Ok(())
+    }
+}
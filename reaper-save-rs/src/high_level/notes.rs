@@ -0,0 +1,138 @@
+//! project and track notes, free text kept in a `<NOTES 0 2>` object with every line of the text
+//! prefixed with `|`. a text ending with a newline ends with an empty `|` line
+use super::*;
+use low_level::AnonymousParameter;
+
+const NOTES: &str = "NOTES";
+
+fn notes_object(object: &Object) -> Option<&Object> {
+    object
+        .values
+        .iter()
+        .filter_map(|entry| entry.as_object())
+        .find(|object| matches_attribute_name_ref(object, NOTES))
+}
+
+/// text of the `<NOTES>` object of `object`, lines joined with `\n`
+fn notes(object: &Object) -> Option<String> {
+    notes_object(object).map(|notes| {
+        notes
+            .values
+            .iter()
+            .filter_map(|entry| entry.as_anonymous_parameter())
+            .filter_map(|AnonymousParameter(line)| line.strip_prefix('|'))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// replaces the text of the `<NOTES>` object of `object`, adding one at `position` when there is
+/// none. `\r\n` and `\n` both end a line
+fn set_notes(object: &mut Object, text: &str, position: usize) {
+    let lines = match text.is_empty() {
+        true => vec![],
+        false => text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .map(|line| Entry::AnonymousParameter(AnonymousParameter(format!("|{line}"))))
+            .collect(),
+    };
+    match object
+        .values
+        .iter_mut()
+        .filter_map(|entry| entry.as_object_mut())
+        .find(|object| matches_attribute_name_ref(object, NOTES))
+    {
+        Some(notes) => notes.values = lines,
+        None => object.values.insert(
+            position,
+            Entry::Object(Object {
+                header: Line {
+                    attribute: AttributeName::new(NOTES.to_owned()),
+                    values: vec![Attribute::Int(Int(0)), Attribute::Int(Int(2))],
+                },
+                values: lines,
+            }),
+        ),
+    }
+}
+
+impl ReaperProject {
+    /// project notes, `None` when the project has no `<NOTES>`
+    pub fn notes(&self) -> Option<String> {
+        notes(&self.inner)
+    }
+    /// REAPER writes the project notes before anything else
+    pub fn set_notes(&mut self, text: &str) {
+        set_notes(&mut self.inner, text, 0)
+    }
+}
+
+impl Track {
+    /// track notes, `None` when the track has no `<NOTES>`
+    pub fn notes(&self) -> Option<String> {
+        notes(&self.inner)
+    }
+    pub fn set_notes(&mut self, text: &str) {
+        let position = self
+            .inner
+            .values
+            .iter()
+            .position(|entry| entry.as_object().is_some())
+            .unwrap_or(self.inner.values.len());
+        set_notes(&mut self.inner, text, position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+    const EXTENSION_STATE: &str = include_str!("../../test_data/extension-state.rpp");
+
+    #[test]
+    fn test_notes() -> Result<()> {
+        let project = ReaperProject::parse_from_str(EXTENSION_STATE)?;
+        assert_eq!(
+            project.notes().as_deref(),
+            Some("Mix notes: bass too loud (again)\n")
+        );
+        assert_eq!(
+            ReaperProject::parse_from_str(EXAMPLE_1)?.notes().as_deref(),
+            Some("")
+        );
+        assert_eq!(project.tracks()[0].notes(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_notes_round_trip() -> Result<()> {
+        let text =
+            "Verse 1: | punctuation; \"quotes\" <and> =signs\n\nsecond paragraph\r\n  indented\n";
+        let mut project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        project.set_notes(text);
+        let reparsed = ReaperProject::parse_from_str(&project.clone().serialize_to_string()?)?;
+        assert_eq!(
+            reparsed.notes().as_deref(),
+            Some(text.replace("\r\n", "\n").as_str())
+        );
+        assert!(reparsed
+            .serialize_to_string()?
+            .starts_with(&low_level::normalize_line_endings(
+                &format!(
+                    "{}\n  <NOTES 0 2\n    |Verse 1: | punctuation; \"quotes\" <and> =signs\n    |\n    |second paragraph\n    |  indented\n    |\n  >\n",
+                    EXAMPLE_1.lines().next().unwrap_or_default()
+                ),
+                LineEnding::CrLf
+            )));
+
+        let mut track = project.tracks().remove(0);
+        track.set_notes("a\nb");
+        assert_eq!(track.notes().as_deref(), Some("a\nb"));
+        track.set_notes("");
+        assert_eq!(track.notes().as_deref(), Some(""));
+        Ok(())
+    }
+}
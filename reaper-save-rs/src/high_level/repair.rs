@@ -0,0 +1,428 @@
+//! integrity checks for projects mangled by hand or by third-party tools. every check
+//! reports what it finds and, when enabled in [`RepairOptions`], fixes it in place
+use super::*;
+use std::collections::HashSet;
+
+/// which problems [`ReaperProject::repair`] is allowed to fix. disabled ones are still reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairOptions {
+    /// rewrite `TRACKID` to match the uid in the `<TRACK {...}>` header
+    pub track_ids: bool,
+    /// give a track fresh identity uids when any of them is already used by an earlier track,
+    /// see [`repair_duplicate_uids`]
+    pub duplicate_uids: bool,
+    /// drop `<ITEM>` objects without a single entry
+    pub empty_items: bool,
+    /// drop `AUXRECV` lines receiving from a track that does not exist
+    pub dangling_receives: bool,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            track_ids: true,
+            duplicate_uids: true,
+            empty_items: true,
+            dangling_receives: true,
+        }
+    }
+}
+
+/// track and item indices are 0-based positions in the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    TrackIdMismatch {
+        track: usize,
+        header: ReaperUid,
+        track_id: Option<ReaperUid>,
+    },
+    /// the track has no uid in its header, so there is nothing to match `TRACKID` against
+    MissingTrackUid {
+        track: usize,
+    },
+    DuplicateUid {
+        track: usize,
+        uid: ReaperUid,
+    },
+    EmptyItem {
+        track: usize,
+        item: usize,
+    },
+    DanglingReceive {
+        track: usize,
+        source: i64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RepairReport {
+    pub repaired: Vec<Issue>,
+    /// problems found but left alone, either disabled or impossible to fix
+    pub unrepaired: Vec<Issue>,
+}
+
+impl RepairReport {
+    fn extend(&mut self, issues: Vec<Issue>, fixed: bool) {
+        match fixed {
+            true => self.repaired.extend(issues),
+            false => self.unrepaired.extend(issues),
+        }
+    }
+    pub fn is_clean(&self) -> bool {
+        self.repaired.is_empty() && self.unrepaired.is_empty()
+    }
+}
+
+/// `TRACKID` line disagreeing with the header uid. tracks without a header uid are reported as
+/// [`Issue::MissingTrackUid`] and never fixed
+pub fn repair_track_ids(tracks: &mut [Track], fix: bool) -> (Vec<Issue>, Vec<Issue>) {
+    let (mut found, mut unfixable) = (vec![], vec![]);
+    for (index, track) in tracks.iter_mut().enumerate() {
+        let Some(header) = track.uid().cloned() else {
+            unfixable.push(Issue::MissingTrackUid { track: index });
+            continue;
+        };
+        let track_id = track
+            .inner
+            .single_attribute("TRACKID")
+            .and_then(|value| value.as_reaper_uid())
+            .cloned();
+        if track_id.as_ref() == Some(&header) {
+            continue;
+        }
+        if fix {
+            set_line(
+                &mut track.inner,
+                "TRACKID",
+                Some(vec![Attribute::ReaperUid(header.clone())]),
+            );
+        }
+        found.push(Issue::TrackIdMismatch {
+            track: index,
+            header,
+            track_id,
+        });
+    }
+    (found, unfixable)
+}
+
+/// visits the uids identifying a track and what is on it: the header uid, `TRACKID`, item
+/// `IGUID`, take `GUID` and `FXID`. other uids, like pooled midi `POOLEDEVTS`, are meant to be
+/// shared between tracks
+fn for_each_identity_uid_mut(track: &mut Track, f: &mut impl FnMut(&mut ReaperUid)) {
+    fn visit(object: &mut Object, f: &mut impl FnMut(&mut ReaperUid)) {
+        let is_item = Item::matches_object(object);
+        for entry in object.values.iter_mut() {
+            match entry {
+                Entry::Object(object) => visit(object, f),
+                Entry::Line(line)
+                    if matches!(
+                        line.attribute.as_ref().as_str(),
+                        "TRACKID" | "IGUID" | "FXID"
+                    ) || (is_item && line.attribute.as_ref() == "GUID") =>
+                {
+                    line.values.iter_mut().for_each(|value| {
+                        if let Attribute::ReaperUid(uid) = value {
+                            f(uid)
+                        }
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+    track.inner.header.values.iter_mut().for_each(|value| {
+        if let Attribute::ReaperUid(uid) = value {
+            f(uid)
+        }
+    });
+    visit(&mut track.inner, f)
+}
+
+fn identity_uids(track: &mut Track) -> Vec<ReaperUid> {
+    let mut uids = vec![];
+    for_each_identity_uid_mut(track, &mut |uid| uids.push(uid.clone()));
+    uids
+}
+
+/// identity uids (see [`for_each_identity_uid_mut`]) shared with an earlier track, reported
+/// once per offending track. fixing regenerates only those, consistently within the track
+pub fn repair_duplicate_uids(tracks: &mut [Track], fix: bool) -> Vec<Issue> {
+    let mut seen = HashSet::new();
+    let mut found = vec![];
+    for (index, track) in tracks.iter_mut().enumerate() {
+        let mut uids = identity_uids(track);
+        if let Some(uid) = uids.iter().find(|uid| seen.contains(*uid)) {
+            found.push(Issue::DuplicateUid {
+                track: index,
+                uid: uid.clone(),
+            });
+            if fix {
+                let mut replacements = HashMap::new();
+                for_each_identity_uid_mut(track, &mut |uid| {
+                    *uid = replacements
+                        .entry(uid.clone())
+                        .or_insert_with(ReaperUid::random)
+                        .clone();
+                });
+                uids = identity_uids(track);
+            }
+        }
+        seen.extend(uids);
+    }
+    found
+}
+
+pub fn repair_empty_items(tracks: &mut [Track], fix: bool) -> Vec<Issue> {
+    let mut found = vec![];
+    for (track_index, track) in tracks.iter_mut().enumerate() {
+        let mut item_index = 0;
+        track.inner.values.retain(|entry| {
+            let Some(item) = entry.as_object().filter(|o| Item::matches_object(o)) else {
+                return true;
+            };
+            if item.values.is_empty() {
+                found.push(Issue::EmptyItem {
+                    track: track_index,
+                    item: item_index,
+                });
+            }
+            item_index += 1;
+            !(fix && item.values.is_empty())
+        });
+    }
+    found
+}
+
+/// `AUXRECV <source track index> ...` pointing past the last track or at the track itself
+pub fn repair_dangling_receives(tracks: &mut [Track], fix: bool) -> Vec<Issue> {
+    let track_count = tracks.len() as i64;
+    let mut found = vec![];
+    for (index, track) in tracks.iter_mut().enumerate() {
+        track.inner.values.retain(|entry| {
+            let source = entry
+                .as_line()
+                .filter(|line| line.attribute.as_ref() == "AUXRECV")
+                .and_then(|line| line.values.first())
+                .and_then(|value| value.as_int())
+                .map(|Int(source)| *source);
+            match source {
+                Some(source) if !(0..track_count).contains(&source) || source == index as i64 => {
+                    found.push(Issue::DanglingReceive {
+                        track: index,
+                        source,
+                    });
+                    !fix
+                }
+                _ => true,
+            }
+        });
+    }
+    found
+}
+
+impl ReaperProject {
+    /// runs every check, fixing what `options` allows. checks run in the order of
+    /// [`RepairOptions`] fields, so duplicates are looked for after track ids are settled
+    pub fn repair(&mut self, options: RepairOptions) -> RepairReport {
+        let mut report = RepairReport::default();
+        let mut tracks = self.tracks();
+        let (mismatched, unfixable) = repair_track_ids(&mut tracks, options.track_ids);
+        report.extend(mismatched, options.track_ids);
+        report.extend(unfixable, false);
+        report.extend(
+            repair_duplicate_uids(&mut tracks, options.duplicate_uids),
+            options.duplicate_uids,
+        );
+        report.extend(
+            repair_empty_items(&mut tracks, options.empty_items),
+            options.empty_items,
+        );
+        report.extend(
+            repair_dangling_receives(&mut tracks, options.dangling_receives),
+            options.dangling_receives,
+        );
+        if !report.repaired.is_empty() {
+            self.modify_tracks(|_| tracks)
+                .expect("a project with tracks is not empty");
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    fn tracks() -> Result<Vec<Track>> {
+        ReaperProject::parse_from_str(EXAMPLE_1).map(|project| project.tracks())
+    }
+
+    fn mismatch_track_id(track: &mut Track) -> ReaperUid {
+        let header = track.uid().cloned().expect("fixture tracks have uids");
+        set_line(
+            &mut track.inner,
+            "TRACKID",
+            Some(vec![Attribute::ReaperUid(ReaperUid::random())]),
+        );
+        header
+    }
+
+    #[test]
+    fn test_example_is_clean() -> Result<()> {
+        let mut project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        assert_eq!(
+            project.repair(RepairOptions::default()),
+            RepairReport::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_track_ids() -> Result<()> {
+        let mut tracks = tracks()?;
+        let header = mismatch_track_id(&mut tracks[1]);
+
+        let (found, unfixable) = repair_track_ids(&mut tracks, false);
+        assert!(matches!(
+            found.as_slice(),
+            [Issue::TrackIdMismatch { track: 1, .. }]
+        ));
+        assert!(unfixable.is_empty());
+
+        repair_track_ids(&mut tracks, true);
+        assert_eq!(
+            tracks[1].inner.single_attribute("TRACKID"),
+            Some(&Attribute::ReaperUid(header))
+        );
+        assert_eq!(repair_track_ids(&mut tracks, true), (vec![], vec![]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_duplicate_uids() -> Result<()> {
+        let mut tracks = tracks()?;
+        tracks.push(tracks[0].clone());
+        let uid = tracks[0].uid().cloned().expect("fixture tracks have uids");
+
+        assert_eq!(
+            repair_duplicate_uids(&mut tracks, true),
+            vec![Issue::DuplicateUid {
+                track: tracks.len() - 1,
+                uid: uid.clone()
+            }]
+        );
+        assert_eq!(tracks[0].uid(), Some(&uid));
+        assert_ne!(tracks.last().and_then(Track::uid), Some(&uid));
+        assert_eq!(repair_duplicate_uids(&mut tracks, true), vec![]);
+        Ok(())
+    }
+
+    /// pooled midi shares its uid between tracks on purpose
+    #[test]
+    fn test_repair_duplicate_uids_keeps_pooled_midi() -> Result<()> {
+        let mut tracks = tracks()?;
+        let pool = Attribute::ReaperUid(ReaperUid::random());
+        for track in &mut tracks[..2] {
+            let item = track
+                .inner
+                .values
+                .iter_mut()
+                .find_map(|entry| entry.as_object_mut().filter(|o| Item::matches_object(o)))
+                .expect("fixture tracks have items");
+            set_line(item, "POOLEDEVTS", Some(vec![pool.clone()]));
+        }
+        assert_eq!(repair_duplicate_uids(&mut tracks, true), vec![]);
+        tracks.push(tracks[0].clone());
+        assert_eq!(repair_duplicate_uids(&mut tracks, true).len(), 1);
+        let copy = tracks.last().expect("pushed above");
+        assert_ne!(copy.uid(), tracks[0].uid());
+        assert_eq!(
+            copy.items()[0].inner.single_attribute("POOLEDEVTS"),
+            Some(&pool)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_empty_items() -> Result<()> {
+        let mut tracks = tracks()?;
+        let item = tracks[0]
+            .inner
+            .values
+            .iter_mut()
+            .find_map(|entry| entry.as_object_mut().filter(|o| Item::matches_object(o)))
+            .expect("fixture track has items");
+        item.values.clear();
+
+        assert_eq!(
+            repair_empty_items(&mut tracks, true),
+            vec![Issue::EmptyItem { track: 0, item: 0 }]
+        );
+        assert!(tracks[0].items().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_dangling_receives() -> Result<()> {
+        let mut tracks = tracks()?;
+        let track_count = tracks.len() as i64;
+        let receive = |source: i64| {
+            Entry::Line(Line {
+                attribute: AttributeName::new("AUXRECV".to_owned()),
+                values: vec![Attribute::Int(Int(source)), Attribute::Int(Int(0))],
+            })
+        };
+        tracks[0].inner.values.push(receive(1));
+        tracks[0].inner.values.push(receive(track_count));
+
+        assert_eq!(
+            repair_dangling_receives(&mut tracks, true),
+            vec![Issue::DanglingReceive {
+                track: 0,
+                source: track_count
+            }]
+        );
+        assert_eq!(
+            tracks[0]
+                .inner
+                .attributes("AUXRECV")
+                .and_then(|v| v.first()),
+            Some(&Attribute::Int(Int(1)))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_project() -> Result<()> {
+        let mut project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        project.modify_tracks(|mut tracks| {
+            mismatch_track_id(&mut tracks[2]);
+            tracks.push(tracks[0].clone());
+            tracks
+        })?;
+        let track_count = project.tracks().len();
+
+        let report = project.repair(RepairOptions {
+            empty_items: false,
+            ..Default::default()
+        });
+        assert!(report.unrepaired.is_empty());
+        assert!(matches!(
+            report.repaired.as_slice(),
+            [
+                Issue::TrackIdMismatch { track: 2, .. },
+                Issue::DuplicateUid { track, .. },
+            ] if *track == track_count - 1
+        ));
+        assert_eq!(project.tracks().len(), track_count);
+        assert!(project.repair(RepairOptions::default()).is_clean());
+
+        let reparsed = ReaperProject::parse_from_str(&project.serialize_to_string()?)?;
+        assert_eq!(reparsed.tracks().len(), track_count);
+        Ok(())
+    }
+}
@@ -0,0 +1,514 @@
+//! track to track routing. receives live on the receiving track as
+//! `AUXRECV <source track> <mode> <volume> <pan> <mute> <mono> <phase> ...`,
+//! where the source is the zero-based position of the sending track in the project.
+//! the master send is `MAINSEND <enabled> <channel offset>` on the sending track
+use super::*;
+use std::collections::BTreeSet;
+
+const AUXRECV: &str = "AUXRECV";
+const MAINSEND: &str = "MAINSEND";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuxReceive {
+    /// zero-based position of the sending track among the project's tracks
+    pub source_track: i64,
+    /// `0` post-fader, `1` pre-fx, `3` post-fx (pre-fader)
+    pub mode: i64,
+    /// linear gain, `1` is 0dB
+    pub volume: f64,
+    /// `-1` (left) to `1` (right)
+    pub pan: f64,
+    pub mute: bool,
+    /// sum to mono
+    pub mono: bool,
+    pub invert_phase: bool,
+    /// channel mapping, pan law (`-1:U` for the default), midi routing and automation mode,
+    /// kept as written
+    pub extra: Vec<Attribute>,
+}
+
+impl AuxReceive {
+    /// post-fader stereo receive at unity gain, as REAPER creates it
+    pub fn new(source_track: i64) -> Self {
+        Self {
+            source_track,
+            mode: 0,
+            volume: 1.,
+            pan: 0.,
+            mute: false,
+            mono: false,
+            invert_phase: false,
+            extra: vec![
+                Attribute::Int(Int(0)),
+                Attribute::Int(Int(0)),
+                Attribute::UNumber(Int(-1)),
+                Attribute::Int(Int(0)),
+                Attribute::Int(Int(-1)),
+                Attribute::String(low_level::ReaperString::SingleQuote(String::new())),
+            ],
+        }
+    }
+
+    fn from_line(line: &Line) -> Result<Self> {
+        let value = |index: usize| {
+            line.values
+                .get(index)
+                .ok_or_else(|| error::Error::MissingAttribute {
+                    attribute: AttributeName::new(AUXRECV.to_owned()),
+                })
+        };
+        let flag = |index: usize| {
+            value(index)
+                .and_then(|v| as_int(AUXRECV, v))
+                .map(|v| v != 0)
+        };
+        Ok(Self {
+            source_track: value(0).and_then(|v| as_int(AUXRECV, v))?,
+            mode: value(1).and_then(|v| as_int(AUXRECV, v))?,
+            volume: value(2).and_then(|v| as_number(AUXRECV, v))?,
+            pan: value(3).and_then(|v| as_number(AUXRECV, v))?,
+            mute: flag(4)?,
+            mono: flag(5)?,
+            invert_phase: flag(6)?,
+            extra: line.values.iter().skip(7).cloned().collect(),
+        })
+    }
+
+    fn into_line(self) -> Line {
+        let flag = |value: bool| Attribute::Int(Int(value as i64));
+        Line {
+            attribute: AttributeName::new(AUXRECV.to_owned()),
+            values: [
+                Attribute::Int(Int(self.source_track)),
+                Attribute::Int(Int(self.mode)),
+                Attribute::Float(OrderedFloat(self.volume)),
+                Attribute::Float(OrderedFloat(self.pan)),
+                flag(self.mute),
+                flag(self.mono),
+                flag(self.invert_phase),
+            ]
+            .into_iter()
+            .chain(self.extra)
+            .collect(),
+        }
+    }
+}
+
+fn is_receive(entry: &Entry) -> bool {
+    entry
+        .as_line()
+        .is_some_and(|line| line.attribute.as_ref() == AUXRECV)
+}
+
+impl Track {
+    /// receives of this track, in the order they are written
+    pub fn receives(&self) -> Result<Vec<AuxReceive>> {
+        self.inner
+            .values
+            .iter()
+            .filter(|entry| is_receive(entry))
+            .filter_map(|entry| entry.as_line())
+            .map(AuxReceive::from_line)
+            .collect()
+    }
+
+    /// appends a receive after the existing ones, or where REAPER puts the first one
+    pub fn add_receive(&mut self, receive: AuxReceive) {
+        let entry = Entry::Line(receive.into_line());
+        let is_line = |name: &'static str| {
+            move |entry: &Entry| {
+                entry
+                    .as_line()
+                    .is_some_and(|line| line.attribute.as_ref() == name)
+            }
+        };
+        if let Err(entry) = self
+            .inner
+            .insert_entry_after(is_receive, entry)
+            .or_else(|entry| self.inner.insert_entry_after(is_line("PERF"), entry))
+            .or_else(|entry| self.inner.insert_entry_after(is_line("TRACKID"), entry))
+        {
+            let position = self
+                .inner
+                .values
+                .iter()
+                .position(|entry| entry.as_object().is_some())
+                .unwrap_or(self.inner.values.len());
+            self.inner.values.insert(position, entry);
+        }
+    }
+
+    /// removes the `index`-th receive (as returned by [`Track::receives`])
+    pub fn remove_receive(&mut self, index: usize) -> Result<Option<AuxReceive>> {
+        let Some(position) = self
+            .inner
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| is_receive(entry))
+            .map(|(position, _)| position)
+            .nth(index)
+        else {
+            return Ok(None);
+        };
+        let removed = self.inner.values.remove(position);
+        removed.as_line().map(AuxReceive::from_line).transpose()
+    }
+
+    /// rewrites the source of every receive with `renumber`, receives it maps to `None` are
+    /// dropped. keeps routing pointing at the same tracks when tracks are removed
+    pub(super) fn renumber_receives(
+        &mut self,
+        renumber: impl Fn(i64) -> Option<i64>,
+    ) -> Result<()> {
+        self.inner.values = std::mem::take(&mut self.inner.values)
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Line(line) if line.attribute.as_ref() == AUXRECV => {
+                    AuxReceive::from_line(&line)
+                        .map(|receive| {
+                            renumber(receive.source_track).map(|source_track| {
+                                Entry::Line(
+                                    AuxReceive {
+                                        source_track,
+                                        ..receive
+                                    }
+                                    .into_line(),
+                                )
+                            })
+                        })
+                        .transpose()
+                }
+                entry => Some(Ok(entry)),
+            })
+            .collect::<Result<_>>()?;
+        Ok(())
+    }
+
+    /// whether the track feeds the master (or its parent folder), `true` when `MAINSEND` is missing
+    pub fn sends_to_master(&self) -> Result<bool> {
+        int_at(&self.inner, MAINSEND, 0)
+            .transpose()
+            .map(|enabled| enabled != Some(0))
+    }
+
+    /// toggles the master send, keeping the channel offset. a missing `MAINSEND` is added with
+    /// offset `0`
+    pub fn set_sends_to_master(&mut self, enabled: bool) -> Result<()> {
+        let enabled = Attribute::Int(Int(enabled as i64));
+        match self.inner.attributes(MAINSEND).is_some() {
+            true => set_attribute_at(&mut self.inner, MAINSEND, 0, enabled),
+            false => {
+                set_line(
+                    &mut self.inner,
+                    MAINSEND,
+                    Some(vec![enabled, Attribute::Int(Int(0))]),
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ReaperProject {
+    /// receives of `track` paired with the track they come from. the source is `None` for
+    /// dangling routing, a source index past the last track
+    pub fn receives_for(&self, track: &Track) -> Result<Vec<(Option<Track>, AuxReceive)>> {
+        let tracks = self.tracks();
+        track.receives().map(|receives| {
+            receives
+                .into_iter()
+                .map(|receive| {
+                    let source = usize::try_from(receive.source_track)
+                        .ok()
+                        .and_then(|index| tracks.get(index))
+                        .cloned();
+                    (source, receive)
+                })
+                .collect()
+        })
+    }
+
+    /// a copy of the project holding only the track with header `uid` and every track it
+    /// receives from, directly or through other receives. tracks keep their order and receive
+    /// sources are renumbered to match, dangling receives are dropped. folders are only kept
+    /// when all of their tracks are extracted, otherwise they are flattened
+    pub fn extract_track(&self, uid: &ReaperUid) -> Result<ReaperProject> {
+        let tracks = self.tracks();
+        let root = tracks
+            .iter()
+            .position(|track| track.uid() == Some(uid))
+            .ok_or_else(|| error::Error::NoSuchTrack { uid: uid.clone() })?;
+        let mut included = BTreeSet::from([root]);
+        let mut pending = vec![root];
+        while let Some(index) = pending.pop() {
+            for receive in tracks[index].receives()? {
+                if let Some(source) = usize::try_from(receive.source_track)
+                    .ok()
+                    .filter(|source| *source < tracks.len())
+                {
+                    if included.insert(source) {
+                        pending.push(source);
+                    }
+                }
+            }
+        }
+        let positions = included
+            .iter()
+            .enumerate()
+            .map(|(position, index)| (*index as i64, position as i64))
+            .collect::<HashMap<_, _>>();
+        let depth_changes = folders::subset_depth_changes(&tracks, &included)?;
+        let extracted = included
+            .iter()
+            .zip(depth_changes)
+            .map(|(index, depth_change)| {
+                let mut track = tracks[*index].clone();
+                track.renumber_receives(|source| positions.get(&source).copied())?;
+                if track.folder_depth_change()? != depth_change {
+                    track.set_folder_depth_change(depth_change);
+                }
+                Ok(track)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut project = self.clone();
+        project.modify_tracks(|_| extracted)?;
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    fn receiving_track() -> Result<Track> {
+        ReaperProject::parse_from_str(EXAMPLE_1)?
+            .tracks()
+            .into_iter()
+            .find(|track| track.inner.attributes(AUXRECV).is_some())
+            .ok_or_else(|| error::Error::MissingAttribute {
+                attribute: AttributeName::new(AUXRECV.to_owned()),
+            })
+    }
+
+    #[test]
+    fn test_receives() -> Result<()> {
+        let track = receiving_track()?;
+        let receives = track.receives()?;
+        assert!(receives.len() > 1);
+        assert_eq!(
+            receives[0],
+            AuxReceive {
+                source_track: 0,
+                ..AuxReceive::new(0)
+            }
+        );
+        assert_eq!(
+            receives
+                .iter()
+                .map(|r| r.source_track)
+                .take(3)
+                .collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_receive_round_trips() -> Result<()> {
+        let track = receiving_track()?;
+        let lines = track
+            .inner
+            .values
+            .iter()
+            .filter(|entry| is_receive(entry))
+            .filter_map(|entry| entry.as_line())
+            .cloned()
+            .collect::<Vec<_>>();
+        for line in lines {
+            let receive = AuxReceive::from_line(&line)?;
+            assert_eq!(
+                receive.into_line().serialize_inline()?,
+                line.serialize_inline()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sends_to_master() -> Result<()> {
+        let tracks = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks();
+        let sends = tracks
+            .iter()
+            .map(Track::sends_to_master)
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(sends.iter().filter(|sends| !**sends).count(), 4);
+
+        let mut track = tracks[0].clone();
+        track.set_sends_to_master(false)?;
+        assert!(!track.sends_to_master()?);
+        assert_eq!(
+            track.inner.attributes(MAINSEND),
+            Some(&vec![Attribute::Int(Int(0)), Attribute::Int(Int(0))])
+        );
+
+        track.inner.remove_entries(|entry| {
+            entry
+                .as_line()
+                .is_some_and(|line| line.attribute.as_ref() == MAINSEND)
+        });
+        assert!(track.sends_to_master()?);
+        track.set_sends_to_master(true)?;
+        assert_eq!(
+            track.inner.attributes(MAINSEND),
+            Some(&vec![Attribute::Int(Int(1)), Attribute::Int(Int(0))])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_receives_for() -> Result<()> {
+        let project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = project.tracks();
+        let mut track = receiving_track()?;
+        track.add_receive(AuxReceive::new(tracks.len() as i64));
+
+        let resolved = project.receives_for(&track)?;
+        let (dangling, resolved) = resolved.split_last().expect("receive added above");
+        assert_eq!(dangling, &(None, AuxReceive::new(tracks.len() as i64)));
+        assert!(!resolved.is_empty());
+        for (source, receive) in resolved {
+            assert_eq!(
+                source.as_ref(),
+                tracks.get(receive.source_track as usize),
+                "{receive:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_track() -> Result<()> {
+        let mut project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        let tracks = project.tracks();
+        let mut source = tracks[1].clone();
+        source.inner.remove_entries(is_receive);
+        let mut receiver = tracks[3].clone();
+        receiver.inner.remove_entries(is_receive);
+        receiver.add_receive(AuxReceive::new(1));
+        receiver.add_receive(AuxReceive::new(tracks.len() as i64));
+        project.modify_tracks(|tracks| {
+            tracks
+                .into_iter()
+                .enumerate()
+                .map(|(index, track)| match index {
+                    1 => source.clone(),
+                    3 => receiver.clone(),
+                    _ => track,
+                })
+                .collect()
+        })?;
+
+        let uid = receiver.uid().cloned().expect("tracks have header uids");
+        let extracted = project.extract_track(&uid)?;
+        let extracted_tracks = extracted.tracks();
+        assert_eq!(
+            extracted_tracks
+                .iter()
+                .map(Track::name)
+                .collect::<Result<Vec<_>>>()?,
+            [tracks[1].name()?, tracks[3].name()?]
+        );
+        assert_eq!(extracted_tracks[1].receives()?, vec![AuxReceive::new(0)]);
+        assert_eq!(extracted.master_track()?, project.master_track()?);
+
+        assert!(matches!(
+            project.extract_track(&ReaperUid::random()),
+            Err(error::Error::NoSuchTrack { .. })
+        ));
+
+        // `(name, ISBUS depth change, receives from)`
+        let folders = ReaperProject::parse_from_str(&format!(
+            "<REAPER_PROJECT\r\n{}>\r\n",
+            [
+                ("a", 1, &[][..]),
+                ("b", 0, &[]),
+                ("c", 0, &[]),
+                ("d", -1, &[0]),
+                ("e", 1, &[]),
+                ("f", -1, &[2]),
+                ("g", 0, &[4, 5]),
+            ]
+            .iter()
+            .enumerate()
+            .map(|(index, (name, change, receives))| {
+                let receives = receives
+                    .iter()
+                    .map(|source| format!("    AUXRECV {source} 0 1 0 0 0 0 0 0 -1:U 0 -1 ''\r\n"))
+                    .collect::<String>();
+                format!(
+                    "  <TRACK {{00000000-0000-0000-0000-{index:012}}}\r\n    NAME {name}\r\n    ISBUS 0 {change}\r\n{receives}  >\r\n"
+                )
+            })
+            .collect::<String>()
+        ))?;
+        let extracted_folders = |index: usize| -> Result<Vec<(String, i64)>> {
+            let uid = ReaperUid(format!("00000000-0000-0000-0000-{index:012}"));
+            folders
+                .extract_track(&uid)?
+                .tracks()
+                .iter()
+                .map(|track| Ok((track.name()?, track.folder_depth_change()?)))
+                .collect()
+        };
+        let owned = |tracks: &[(&str, i64)]| {
+            tracks
+                .iter()
+                .map(|(name, change)| (name.to_string(), *change))
+                .collect::<Vec<_>>()
+        };
+        // the receiving child doesn't stay inside the folder it receives from
+        assert_eq!(extracted_folders(3)?, owned(&[("a", 0), ("d", 0)]));
+        // no stray folder end for a child extracted without its parent
+        assert_eq!(extracted_folders(5)?, owned(&[("c", 0), ("f", 0)]));
+        // a folder extracted with all of its tracks is kept
+        assert_eq!(
+            extracted_folders(6)?,
+            owned(&[("c", 0), ("e", 1), ("f", -1), ("g", 0)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_remove_receive() -> Result<()> {
+        let mut track = receiving_track()?;
+        let count = track.receives()?.len();
+        track.add_receive(AuxReceive {
+            volume: 0.5,
+            mono: true,
+            ..AuxReceive::new(7)
+        });
+        let receives = track.receives()?;
+        assert_eq!(receives.len(), count + 1);
+        assert!(track
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  AUXRECV 7 0 0.5 0 0 1 0 0 0 -1:U 0 -1 ''\r\n  MIDIOUT"));
+
+        assert_eq!(track.remove_receive(count)?, receives.last().cloned());
+        assert_eq!(track.remove_receive(count)?, None);
+        assert_eq!(track.receives()?.len(), count);
+
+        let mut track = ReaperProject::parse_from_str(EXAMPLE_1)?.tracks().remove(0);
+        assert_eq!(track.receives()?, vec![]);
+        track.add_receive(AuxReceive::new(1));
+        assert!(track
+            .inner
+            .serialize_inline()?
+            .contains("\r\n  PERF 0\r\n  AUXRECV 1 0 1 0 0 0 0 0 0 -1:U 0 -1 ''\r\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+//! takes of an item. they are not objects of their own: the first take starts at the first
+//! take property (`NAME`, or the `<SOURCE>` of a take without a name) after the item's own
+//! lines, every further take starts after a `TAKE` line. `TAKE SEL` marks the active take,
+//! `TAKE NULL` an empty one
+use super::*;
+use std::ops::Range;
+
+const TAKE: &str = "TAKE";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Take {
+    /// the active take, the one that plays
+    pub selected: bool,
+    /// everything belonging to the take: `NAME`, `SOFFS`, `GUID`, `<SOURCE>`, take envelopes...
+    pub entries: Vec<Entry>,
+}
+
+impl Take {
+    /// contents of the take's `NAME`, `None` for empty takes
+    pub fn name(&self) -> Option<String> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.as_line())
+            .find(|line| line.attribute.as_ref() == "NAME")
+            .and_then(|line| line.values.first())
+            .map(|name| match name.as_str() {
+                Some(name) => name.to_owned(),
+                None => name.serialize_inline().unwrap_or_default(),
+            })
+    }
+    pub fn source_wave(&self) -> Option<SourceWave> {
+        self.entries
+            .iter()
+            .filter_map(|e| e.as_object())
+            .find(|o| SourceWave::matches_object(o))
+            .cloned()
+            .map(SourceWave::from_object_raw)
+    }
+    /// a `TAKE NULL` placeholder, kept so the takes after it stay in their lanes
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Item {
+    /// where each take is among the item's entries and whether it is selected
+    fn take_ranges(&self) -> Vec<(bool, Range<usize>)> {
+        let values = &self.inner.values;
+        let take_lines = values
+            .iter()
+            .enumerate()
+            .filter_map(|(position, entry)| {
+                entry
+                    .as_line()
+                    .filter(|line| line.attribute.as_ref() == TAKE)
+                    .map(|line| (position, line))
+            })
+            .collect::<Vec<_>>();
+        let first_end = take_lines
+            .first()
+            .map_or(values.len(), |(position, _)| *position);
+        let first_start = values[..first_end].iter().position(|entry| match entry {
+            Entry::Line(line) => line.attribute.as_ref() == "NAME",
+            Entry::Object(object) => SourceWave::matches_object(object),
+            _ => false,
+        });
+        let first = match (first_start, take_lines.is_empty()) {
+            (None, true) => None,
+            (start, _) => Some((false, start.unwrap_or(first_end)..first_end)),
+        };
+        let mut ranges = first
+            .into_iter()
+            .chain(
+                take_lines
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (position, line))| {
+                        let end = take_lines
+                            .get(index + 1)
+                            .map_or(values.len(), |(next, _)| *next);
+                        let selected = line
+                            .values
+                            .iter()
+                            .any(|value| value.as_str() == Some("SEL"));
+                        (selected, position + 1..end)
+                    }),
+            )
+            .collect::<Vec<_>>();
+        if !ranges.iter().any(|(selected, _)| *selected) {
+            if let Some((selected, _)) = ranges.first_mut() {
+                *selected = true;
+            }
+        }
+        ranges
+    }
+
+    /// takes in lane order. items without any take (empty and notes items) have none
+    pub fn takes(&self) -> Vec<Take> {
+        self.take_ranges()
+            .into_iter()
+            .map(|(selected, range)| Take {
+                selected,
+                entries: self.inner.values[range].to_vec(),
+            })
+            .collect()
+    }
+    pub fn take_count(&self) -> usize {
+        self.take_ranges().len()
+    }
+    /// the take that plays, see [`Take::selected`]
+    pub fn active_take(&self) -> Option<Take> {
+        self.takes().into_iter().find(|take| take.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+    const MULTI_TAKE_ITEM: &str = r#"<TRACK
+  <ITEM
+    POSITION 0
+    LENGTH 2
+    ALLTAKES 0
+    IGUID {FCC35993-4DC1-33FD-5760-A9F4788CC17D}
+    IID 1
+    NAME first.wav
+    VOLPAN 1 0 1 -1
+    SOFFS 0
+    GUID {DFACF98E-3268-F4C2-4A25-C98E3031C1D1}
+    <SOURCE WAVE
+      FILE "first.wav"
+    >
+    TAKE NULL
+    TAKE SEL
+    NAME "second take"
+    TAKEVOLPAN 0 1 -1
+    SOFFS 0.5
+    GUID {DFACF98E-3268-F4C2-4A25-C98E3031C1D2}
+    <SOURCE WAVE
+      FILE "second.wav"
+    >
+  >
+>
+"#;
+
+    fn file(take: &Take) -> Option<String> {
+        take.source_wave()?.file()?.ok().map(str::to_owned)
+    }
+
+    #[test]
+    fn test_takes() -> Result<()> {
+        let item = Track::from_track_template_str(MULTI_TAKE_ITEM)?[0].items()[0].clone();
+        let takes = item.takes();
+        assert_eq!(item.take_count(), 3);
+        assert_eq!(
+            takes
+                .iter()
+                .map(|take| (take.selected, take.name(), file(take)))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    false,
+                    Some("first.wav".to_owned()),
+                    Some("first.wav".to_owned())
+                ),
+                (false, None, None),
+                (
+                    true,
+                    Some("second take".to_owned()),
+                    Some("second.wav".to_owned())
+                ),
+            ]
+        );
+        assert!(takes[1].is_empty());
+        assert_eq!(takes[0].entries.len(), 5);
+        assert_eq!(item.active_take().as_ref(), takes.get(2));
+        // the item's own lines belong to no take
+        assert!(takes
+            .iter()
+            .flat_map(|take| &take.entries)
+            .filter_map(|entry| entry.as_line())
+            .all(|line| line.attribute.as_ref() != "POSITION"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_take() -> Result<()> {
+        let project = ReaperProject::parse_from_str(EXAMPLE_1)?;
+        for (_, item) in project.all_items() {
+            let takes = item.takes();
+            assert_eq!(takes.len(), 1);
+            assert!(takes[0].selected);
+            assert!(takes[0].source_wave() == item.source_wave());
+        }
+        let empty = Item::from_object(low_level::parse_object("<ITEM\n  POSITION 0\n>")?)?;
+        assert_eq!(empty.take_count(), 0);
+        assert_eq!(empty.active_take(), None);
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
-#![feature(extract_if)]
-
 pub mod high_level;
 pub mod low_level;
 
 pub mod prelude {
-    pub use crate::high_level::{Item, ObjectWrapper, ReaperProject, Track};
-    pub use crate::low_level::SerializeAndDeserialize;
+    pub use crate::high_level::{
+        Envelope, FxChain, Item, MasterTrack, ObjectWrapper, ReaperProject, RenderCfg, SourceWave,
+        Track,
+    };
+    pub use crate::low_level::{
+        AnonymousParameter, Attribute, AttributeKind, AttributeName, Entry, Int, Line, LineEnding,
+        Object, ParseOptions, ReaperString, ReaperUid, SerializeAndDeserialize, SerializeOptions,
+    };
 }
@@ -0,0 +1,261 @@
+//! REAPER's order of lines, for objects built or edited by hand. REAPER writes the lines of an
+//! object in a fixed order and re-saving a project puts them back in it, so matching it up
+//! front keeps diffs against REAPER's output quiet
+use super::*;
+
+/// lines of `<REAPER_PROJECT>`, `<TRACK>` and `<ITEM>` in the order REAPER 6 and 7 write them.
+/// take lines of an item come after the item's own ones
+const ORDERS: &[(&str, &[&str])] = &[
+    (
+        "REAPER_PROJECT",
+        &[
+            "RIPPLE",
+            "GROUPOVERRIDE",
+            "AUTOXFADE",
+            "ENVATTACH",
+            "POOLEDENVATTACH",
+            "MIXERUIFLAGS",
+            "PEAKGAIN",
+            "FEEDBACK",
+            "PANLAW",
+            "PROJOFFS",
+            "MAXPROJLEN",
+            "GRID",
+            "TIMEMODE",
+            "VIDEO_CONFIG",
+            "PANMODE",
+            "CURSOR",
+            "ZOOM",
+            "VZOOMEX",
+            "USE_REC_CFG",
+            "RECMODE",
+            "SMPTESYNC",
+            "LOOP",
+            "LOOPGRAN",
+            "RECORD_PATH",
+            "RENDER_FILE",
+            "RENDER_PATTERN",
+            "RENDER_FMT",
+            "RENDER_1X",
+            "RENDER_RANGE",
+            "RENDER_RESAMPLE",
+            "RENDER_ADDTOPROJ",
+            "RENDER_STEMS",
+            "RENDER_DITHER",
+            "TIMELOCKMODE",
+            "TEMPOENVLOCKMODE",
+            "ITEMMIX",
+            "DEFPITCHMODE",
+            "TAKELANE",
+            "SAMPLERATE",
+            "LOCK",
+            "GLOBAL_AUTO",
+            "TEMPO",
+            "PLAYRATE",
+            "SELECTION",
+            "SELECTION2",
+            "MASTERAUTOMODE",
+            "MASTERTRACKHEIGHT",
+            "MASTERPEAKCOL",
+            "MASTERMUTESOLO",
+            "MASTERTRACKVIEW",
+            "MASTERHWOUT",
+            "MASTER_NCH",
+            "MASTER_VOLUME",
+            "MASTER_PANMODE",
+            "MASTER_FX",
+            "MASTER_SEL",
+        ],
+    ),
+    (
+        "TRACK",
+        &[
+            "NAME",
+            "PEAKCOL",
+            "BEAT",
+            "AUTOMODE",
+            "PANLAWFLAGS",
+            "VOLPAN",
+            "MUTESOLO",
+            "IPHASE",
+            "PLAYOFFS",
+            "ISBUS",
+            "BUSCOMP",
+            "SHOWINMIX",
+            "FIXEDLANES",
+            "SEL",
+            "REC",
+            "VU",
+            "SPACER",
+            "TRACKHEIGHT",
+            "INQ",
+            "NCHAN",
+            "FX",
+            "TRACKID",
+            "PERF",
+            "AUXRECV",
+            "MIDIOUT",
+            "MAINSEND",
+        ],
+    ),
+    (
+        "ITEM",
+        &[
+            "POSITION", "SNAPOFFS", "LENGTH", "LOOP", "ALLTAKES", "FADEIN", "FADEOUT", "MUTE",
+            "SEL", "IGUID", "IID", "NAME", "VOLPAN", "SOFFS", "PLAYRATE", "CHANMODE", "GUID",
+            "RECPASS",
+        ],
+    ),
+];
+
+/// entries of an item starting a take, so lines never move to another take. the first take
+/// has no `TAKE` line and starts at its `NAME`
+fn take_starts(item: &Object) -> Vec<usize> {
+    let first = item.values.iter().position(|entry| {
+        entry
+            .as_line()
+            .is_some_and(|line| matches!(line.attribute.0.as_str(), "NAME" | "TAKE"))
+    });
+    first
+        .into_iter()
+        .chain(item.values.iter().enumerate().filter_map(|(index, entry)| {
+            entry
+                .as_line()
+                .filter(|line| line.attribute.0 == "TAKE")
+                .map(|_| index)
+        }))
+        .collect()
+}
+
+impl Object {
+    /// puts the known lines of this object and every object inside it in REAPER's order.
+    /// opt-in, parsing and serializing never reorder anything. only `<REAPER_PROJECT>`,
+    /// `<TRACK>` and `<ITEM>` have a known order, and in them lines REAPER's order does not
+    /// cover stay where they are. lines only move within a run of lines, never past a child
+    /// object (or from one take of an item to another)
+    pub fn canonicalize(&mut self) {
+        let order = ORDERS
+            .iter()
+            .find(|(name, _)| *name == self.header.attribute.as_ref())
+            .map(|(_, order)| *order);
+        if let Some(order) = order {
+            let take_starts = match self.header.attribute.as_ref() == "ITEM" {
+                true => take_starts(self),
+                false => vec![],
+            };
+            let mut start = 0;
+            while start < self.values.len() {
+                let end = (start + 1..=self.values.len())
+                    .find(|end| {
+                        take_starts.contains(end)
+                            || self
+                                .values
+                                .get(*end)
+                                .is_none_or(|entry| entry.as_line().is_none())
+                    })
+                    .unwrap_or(self.values.len());
+                sort_known_lines(&mut self.values[start..end], order);
+                start = end;
+            }
+        }
+        self.values
+            .iter_mut()
+            .filter_map(|entry| entry.as_object_mut())
+            .for_each(Object::canonicalize);
+    }
+}
+
+/// sorts the lines of `run` listed in `order` among the places they take up, leaving the rest
+fn sort_known_lines(run: &mut [Entry], order: &[&str]) {
+    let rank = |entry: &Entry| {
+        entry.as_line().and_then(|line| {
+            order
+                .iter()
+                .position(|name| *name == line.attribute.as_ref())
+        })
+    };
+    let slots = run
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| rank(entry).is_some())
+        .map(|(slot, _)| slot)
+        .collect::<Vec<_>>();
+    let mut known = slots
+        .iter()
+        .map(|slot| std::mem::replace(&mut run[*slot], Entry::Blank))
+        .collect::<Vec<_>>();
+    // stable, so repeated lines like `AUXRECV` keep their order
+    known.sort_by_key(|entry| rank(entry));
+    for (slot, entry) in slots.into_iter().zip(known) {
+        run[slot] = entry;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    /// puts the known lines before the first child object in the reverse of REAPER's order
+    fn shuffle(object: &mut Object) {
+        let (_, order) = ORDERS
+            .iter()
+            .find(|(name, _)| *name == object.header.attribute.as_ref())
+            .unwrap();
+        let reversed = order.iter().rev().copied().collect::<Vec<_>>();
+        let lines = object
+            .values
+            .iter()
+            .position(|entry| entry.as_object().is_some())
+            .unwrap_or(object.values.len());
+        sort_known_lines(&mut object.values[..lines], &reversed);
+    }
+
+    #[test]
+    fn test_canonicalize_is_a_no_op_for_reaper_output() -> error::Result<()> {
+        let original = from_str(EXAMPLE_1)?;
+        let mut canonical = original.clone();
+        canonical.canonicalize();
+        assert_eq!(canonical, original);
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize() -> error::Result<()> {
+        let original = from_str(EXAMPLE_1)?;
+        let mut shuffled = original.clone();
+        shuffle(&mut shuffled);
+        for track in shuffled
+            .values
+            .iter_mut()
+            .filter_map(|entry| entry.as_object_mut())
+            .filter(|object| object.header.attribute.as_ref() == "TRACK")
+        {
+            shuffle(track);
+        }
+        assert_ne!(shuffled, original);
+        shuffled.canonicalize();
+        assert_eq!(shuffled, original);
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize_keeps_unknown_lines_and_takes() -> error::Result<()> {
+        let mut item = parse_object(
+            "<ITEM\n  LENGTH 2\n  CUSTOM 1\n  POSITION 0\n  SOFFS 0\n  NAME a\n  TAKE\n  GUID {A7C909DB-4DAD-B892-B4F5-41897CECF546}\n  NAME b\n>",
+        )?;
+        item.canonicalize();
+        assert_eq!(
+            item.serialize_inline()?,
+            "<ITEM\r\n  POSITION 0\r\n  CUSTOM 1\r\n  LENGTH 2\r\n  SOFFS 0\r\n  NAME a\r\n  TAKE\r\n  NAME b\r\n  GUID {A7C909DB-4DAD-B892-B4F5-41897CECF546}\r\n>"
+        );
+
+        let mut unknown = parse_object("<SOURCE WAVE\n  FILE a.wav\n  A 1\n>")?;
+        let before = unknown.clone();
+        unknown.canonicalize();
+        assert_eq!(unknown, before);
+        Ok(())
+    }
+}
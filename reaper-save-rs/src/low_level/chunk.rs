@@ -0,0 +1,131 @@
+//! binary state REAPER stores base64 encoded in the anonymous lines of an object: plugin
+//! state in `<VST>`, the render format in `<RENDER_CFG>` and so on
+use super::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// REAPER never writes longer chunk lines
+const CHUNK_LINE_WIDTH: usize = 128;
+/// plugin objects of an fx chain, the first value of their header is the name shown in REAPER
+const PLUGIN_HEADERS: &[&str] = &["VST", "AU", "CLAP", "DX", "LV2", "JS"];
+
+/// text of a chunk line. one made of capital letters and digits only (like `AAAAAAAA`) reads as
+/// a line without values, so those count as well
+fn chunk_line(entry: &Entry) -> Option<&str> {
+    match entry {
+        Entry::AnonymousParameter(AnonymousParameter(line)) => Some(line),
+        Entry::Line(Line { attribute, values })
+            if values.is_empty() && is_base64_line(attribute.as_ref()) =>
+        {
+            Some(attribute.as_ref())
+        }
+        _ => None,
+    }
+}
+
+impl Object {
+    /// name of the plugin this object holds the state of, like `VST: ReaEQ (Cockos)`
+    fn plugin_name(&self) -> Option<String> {
+        PLUGIN_HEADERS
+            .contains(&self.header.attribute.as_ref().as_str())
+            .then(|| self.header.values.first())
+            .flatten()
+            .map(|name| match name.as_str() {
+                Some(name) => Ok(name.to_owned()),
+                None => name.serialize_inline(),
+            })
+            .and_then(Result::ok)
+    }
+
+    /// bytes of every base64 line of this object, each decoded on its own and concatenated.
+    /// a broken chunk of a plugin is a [`error::Error::ChunkDecode`] naming the plugin
+    pub fn chunk(&self) -> error::Result<Vec<u8>> {
+        self.values
+            .iter()
+            .filter_map(chunk_line)
+            .try_fold(Vec::new(), |mut chunk, line| {
+                STANDARD.decode_vec(line, &mut chunk).map(|()| chunk)
+            })
+            .map_err(|source| match self.plugin_name() {
+                Some(plugin) => error::Error::ChunkDecode { plugin, source },
+                None => source.into(),
+            })
+    }
+
+    /// replaces the base64 lines of this object with `chunk`, encoded and wrapped the way
+    /// REAPER does it. the new lines go where the first old one was, after everything else
+    /// when there were none
+    pub fn set_chunk(&mut self, chunk: &[u8]) {
+        let position = self
+            .values
+            .iter()
+            .position(|entry| chunk_line(entry).is_some())
+            .unwrap_or(self.values.len());
+        self.values.retain(|entry| chunk_line(entry).is_none());
+        let encoded = STANDARD.encode(chunk);
+        self.values.splice(
+            position..position,
+            encoded.as_bytes().chunks(CHUNK_LINE_WIDTH).map(|line| {
+                Entry::AnonymousParameter(AnonymousParameter(
+                    String::from_utf8_lossy(line).into_owned(),
+                ))
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::Result;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_chunk() -> Result<()> {
+        let mut object = from_str("<RENDER_CFG\r\n  ZXZhdxgAAQ==\r\n>")?;
+        assert_eq!(object.chunk()?, b"evaw\x18\x00\x01");
+
+        let long = (0..=255).collect::<Vec<u8>>();
+        object.set_chunk(&long);
+        assert_eq!(object.values.len(), 3);
+        assert_eq!(object.chunk()?, long);
+        let serialized = to_string(object)?;
+        assert_eq!(from_str(&serialized)?.chunk()?, long);
+
+        let mut vst = from_str("<VST a\r\n  AAAA\r\n  BBBB\r\n  PRESETNAME x\r\n>")?;
+        assert_eq!(vst.chunk()?, [0, 0, 0, 4, 16, 65]);
+        vst.set_chunk(b"x");
+        assert_eq!(
+            to_string(vst)?,
+            "<VST a\r\n  eA==\r\n  PRESETNAME x\r\n>\r\n"
+        );
+
+        let broken = from_str("<RENDER_CFG\r\n  A===\r\n>")?;
+        assert!(matches!(
+            broken.chunk(),
+            Err(error::Error::InvalidChunk { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunk_error_names_the_plugin() -> Result<()> {
+        let broken = from_str(
+            "<VST \"VST: ReaEQ (Cockos)\" reaeq.dll 0 \"\" 1919247729<56535472656571726561657100000000> \"\"\r\n  A===\r\n>",
+        )?;
+        match broken.chunk() {
+            Err(error @ error::Error::ChunkDecode { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "Chunk of plugin VST: ReaEQ (Cockos) is not valid base64"
+                );
+            }
+            other => panic!("expected ChunkDecode, got {other:?}"),
+        }
+        let unnamed = from_str("<VST\r\n  A===\r\n>")?;
+        assert!(matches!(
+            unnamed.chunk(),
+            Err(error::Error::InvalidChunk { .. })
+        ));
+        Ok(())
+    }
+}
@@ -6,10 +6,39 @@ pub enum Error {
         #[from]
         source: std::fmt::Error,
     },
+    #[error("Reading or writing the underlying stream failed")]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
     #[error("Writing whitespace failed")]
     WriteWhitespaceError,
+    #[error("Line has an empty attribute name, it would not parse back")]
+    EmptyAttributeName,
     #[error("Failed to parse:\n{report}")]
     ParseError { report: String },
+    #[error("Object <{header}> opened at byte {opened_at} is never closed")]
+    UnterminatedObject { header: String, opened_at: usize },
+    #[error("{{{uid}}} is not a uid of the form {{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}}")]
+    InvalidUid { uid: String },
+    #[error("Unexpected input after the parsed value: {remaining:?}")]
+    TrailingData { remaining: String },
+    #[error("Object at byte {at} is nested deeper than {max_depth} levels")]
+    MaxDepthExceeded { max_depth: usize, at: usize },
+    #[error("{path} is not a path like TRACK[2]/VOLPAN[1]")]
+    InvalidPath { path: String },
+    #[error("Nothing found at {path}")]
+    PathNotFound { path: String },
+    #[error("Chunk is not valid base64")]
+    InvalidChunk {
+        #[from]
+        source: base64::DecodeError,
+    },
+    #[error("Chunk of plugin {plugin} is not valid base64")]
+    ChunkDecode {
+        plugin: String,
+        source: base64::DecodeError,
+    },
     #[error("Param {param} not found in object")]
     ObjectNoSuchParam { param: String },
     #[error("Expected for object parameter to have {expected} attributes, but it has {found}")]
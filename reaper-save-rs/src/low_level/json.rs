@@ -0,0 +1,88 @@
+//! debugging view of the AST where every value carries the kind it was parsed as,
+//! e.g. `{"type":"float","value":0.25}`
+use super::*;
+use serde_json::{json, Value};
+
+impl Attribute {
+    pub fn to_typed_json(&self) -> Value {
+        match self {
+            Attribute::ReaperUid(ReaperUid(uid)) => json!({"type": "uid", "value": uid}),
+            Attribute::Int(Int(value)) => json!({"type": "int", "value": value}),
+            Attribute::UNumber(Int(value)) => json!({"type": "unumber", "value": value}),
+            Attribute::UInt(value) => json!({"type": "uint", "value": value}),
+            Attribute::Suffixed {
+                value: Int(value),
+                suffix,
+            } => json!({"type": "suffixed", "value": value, "suffix": suffix}),
+            Attribute::Float(value) => json!({"type": "float", "value": value.0}),
+            Attribute::String(string) => {
+                let quote = match string {
+                    ReaperString::SingleQuote(_) => "single",
+                    ReaperString::DoubleQuote(_) => "double",
+                    ReaperString::Backtick(_) => "backtick",
+                    ReaperString::Unquoted(_) => "none",
+                };
+                json!({"type": "string", "quote": quote, "value": string.as_ref()})
+            }
+        }
+    }
+}
+
+impl Line {
+    pub fn to_typed_json(&self) -> Value {
+        json!({
+            "type": "line",
+            "name": self.attribute.as_ref(),
+            "values": self.values.iter().map(Attribute::to_typed_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Entry {
+    pub fn to_typed_json(&self) -> Value {
+        match self {
+            Entry::Object(object) => object.to_typed_json(),
+            Entry::Line(line) => line.to_typed_json(),
+            Entry::AnonymousParameter(AnonymousParameter(value)) => {
+                json!({"type": "anonymous", "value": value})
+            }
+            Entry::Blank => json!({"type": "blank"}),
+        }
+    }
+}
+
+impl Object {
+    pub fn to_typed_json(&self) -> Value {
+        json!({
+            "type": "object",
+            "name": self.header.attribute.as_ref(),
+            "values": self.header.values.iter().map(Attribute::to_typed_json).collect::<Vec<_>>(),
+            "entries": self.values.iter().map(Entry::to_typed_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::Result;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_typed_json_annotates_kinds() -> Result<()> {
+        let object = from_str("<METRONOME 6 2\r\n  VOL 0.25 0.125\r\n  SAMPLES \"\" ''\r\n>")?;
+        let json = object.to_typed_json();
+        assert_eq!(json["name"], "METRONOME");
+        assert_eq!(json["values"][0], json!({"type": "int", "value": 6}));
+        assert_eq!(json["entries"][0]["name"], "VOL");
+        assert_eq!(
+            json["entries"][0]["values"][0],
+            json!({"type": "float", "value": 0.25})
+        );
+        assert_eq!(
+            json["entries"][1]["values"][1],
+            json!({"type": "string", "quote": "single", "value": ""})
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+//! best-effort parsing of damaged files, for repair tools. [`from_str_lenient`] goes over the
+//! input line by line, fixing what it can and dropping what it cannot read, reports every
+//! change as a [`ParseWarning`] and then parses the result strictly
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// indentation not matching the nesting (or using tabs) was rewritten. lines are
+    /// counted from 1
+    Reindented { line: usize },
+    /// spaces or tabs at the end of the line were dropped
+    TrailingWhitespace { line: usize },
+    /// line that is not an entry, or lies outside the root object, was dropped. an object
+    /// with an unreadable header is dropped with everything inside it
+    SkippedLine { line: usize, text: String },
+    /// value that starts like a quoted string or uid but is not one, kept as an unquoted string
+    UnquotedFallback { line: usize, token: String },
+    /// object still open at the end of the input was closed there
+    UnclosedObject { header: String },
+    /// the repaired input still did not parse
+    Unparsable { reason: String },
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseWarning::Reindented { line } => write!(f, "line {line}: fixed indentation"),
+            ParseWarning::TrailingWhitespace { line } => {
+                write!(f, "line {line}: dropped trailing whitespace")
+            }
+            ParseWarning::SkippedLine { line, text } => write!(f, "line {line}: skipped {text:?}"),
+            ParseWarning::UnquotedFallback { line, token } => {
+                write!(f, "line {line}: {token} read as an unquoted string")
+            }
+            ParseWarning::UnclosedObject { header } => write!(f, "<{header}> closed at the end"),
+            ParseWarning::Unparsable { reason } => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// parsed `content` of a line that is not `>`, `None` when it cannot be read on its own
+fn parse_content(content: &str) -> Option<Entry> {
+    match content.strip_prefix('<') {
+        Some(header) => parse_line(header).ok().map(Entry::Line),
+        None => parse_entry(content).ok(),
+    }
+}
+
+/// the root object of `input` as far as it can be read, with everything that had to be fixed
+/// or dropped on the way. `None` only when nothing usable is left
+pub fn from_str_lenient(input: &str) -> (Option<Object>, Vec<ParseWarning>) {
+    let mut warnings = vec![];
+    let mut repaired = String::with_capacity(input.len());
+    let mut open: Vec<String> = vec![];
+    let mut root_closed = false;
+    // nesting inside an object being dropped for its unreadable header
+    let mut skipping = 0usize;
+    for (index, raw) in input.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim_end_matches([' ', '\t']);
+        let content = trimmed.trim_start_matches([' ', '\t']);
+        let indentation = &trimmed[..trimmed.len() - content.len()];
+        let skipped = || ParseWarning::SkippedLine {
+            line,
+            text: raw.to_owned(),
+        };
+        if skipping > 0 {
+            match content {
+                ">" => skipping -= 1,
+                _ if content.starts_with('<') => skipping += 1,
+                _ => {}
+            }
+            continue;
+        }
+        if content.is_empty() || (open.is_empty() && (root_closed || !content.starts_with('<'))) {
+            warnings.push(skipped());
+            continue;
+        }
+        if trimmed.len() != raw.len() {
+            warnings.push(ParseWarning::TrailingWhitespace { line });
+        }
+        let depth = match content {
+            ">" => {
+                open.pop();
+                root_closed = open.is_empty();
+                open.len()
+            }
+            _ => {
+                let Some(entry) = parse_content(content) else {
+                    warnings.push(skipped());
+                    skipping = content.starts_with('<') as usize;
+                    continue;
+                };
+                if let Some(values) = entry.as_line().map(|line| &line.values) {
+                    warnings.extend(
+                        values
+                            .iter()
+                            .filter_map(|value| value.as_string()?.as_unquoted())
+                            .filter(|token| token.starts_with(['"', '\'', '`', '{']))
+                            .map(|token| ParseWarning::UnquotedFallback {
+                                line,
+                                token: token.clone(),
+                            }),
+                    );
+                }
+                let depth = open.len();
+                if let Some(Entry::Line(header)) = content.starts_with('<').then_some(entry) {
+                    open.push(header.attribute.as_ref().to_owned());
+                }
+                depth
+            }
+        };
+        let indent = " ".repeat(depth * INDENT_SPACES);
+        if indentation != indent {
+            warnings.push(ParseWarning::Reindented { line });
+        }
+        writeln!(repaired, "{indent}{content}").expect("writing to a string");
+    }
+    while let Some(header) = open.pop() {
+        writeln!(repaired, "{}>", " ".repeat(open.len() * INDENT_SPACES))
+            .expect("writing to a string");
+        warnings.push(ParseWarning::UnclosedObject { header });
+    }
+    match from_str(&repaired) {
+        Ok(object) => (Some(object), warnings),
+        Err(error) => {
+            warnings.push(ParseWarning::Unparsable {
+                reason: error.to_string(),
+            });
+            (None, warnings)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    #[test]
+    fn test_clean_input() -> error::Result<()> {
+        let (object, warnings) = from_str_lenient(EXAMPLE_1);
+        assert_eq!(warnings, []);
+        assert_eq!(object, Some(from_str(EXAMPLE_1)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_damaged_input() -> error::Result<()> {
+        let input = [
+            "junk before the root",
+            "<REAPER_PROJECT 0.1 \"6.80/win64\" 1681653583",
+            "  RIPPLE 0 ",
+            "\t<TRACK {EF045EFF-07D6-5EC9-F31B-3EC7C14D9BC8}",
+            "      NAME \"unterminated",
+            "    NA-ME 1",
+            "",
+            "    <BRO-KEN",
+            "      VOLPAN 1 0",
+            "      <NESTED",
+            "      >",
+            "    >",
+            "    <ITEM",
+            "      POSITION 2",
+        ]
+        .join("\r\n");
+        let (object, warnings) = from_str_lenient(&input);
+        assert_eq!(
+            warnings,
+            [
+                ParseWarning::SkippedLine {
+                    line: 1,
+                    text: "junk before the root".to_owned()
+                },
+                ParseWarning::TrailingWhitespace { line: 3 },
+                ParseWarning::Reindented { line: 4 },
+                ParseWarning::UnquotedFallback {
+                    line: 5,
+                    token: "\"unterminated".to_owned()
+                },
+                ParseWarning::Reindented { line: 5 },
+                ParseWarning::SkippedLine {
+                    line: 6,
+                    text: "    NA-ME 1".to_owned()
+                },
+                ParseWarning::SkippedLine {
+                    line: 7,
+                    text: String::new()
+                },
+                ParseWarning::SkippedLine {
+                    line: 8,
+                    text: "    <BRO-KEN".to_owned()
+                },
+                ParseWarning::UnclosedObject {
+                    header: "ITEM".to_owned()
+                },
+                ParseWarning::UnclosedObject {
+                    header: "TRACK".to_owned()
+                },
+                ParseWarning::UnclosedObject {
+                    header: "REAPER_PROJECT".to_owned()
+                },
+            ]
+        );
+        assert_eq!(
+            object.map(to_string).transpose()?.as_deref(),
+            Some(
+                [
+                    "<REAPER_PROJECT 0.1 \"6.80/win64\" 1681653583",
+                    "  RIPPLE 0",
+                    "  <TRACK {EF045EFF-07D6-5EC9-F31B-3EC7C14D9BC8}",
+                    "    NAME \"unterminated",
+                    "    <ITEM",
+                    "      POSITION 2",
+                    "    >",
+                    "  >",
+                    ">",
+                    "",
+                ]
+                .join("\r\n")
+                .as_str()
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unusable_input() {
+        let (object, warnings) = from_str_lenient("no root here\n>\n");
+        assert_eq!(object, None);
+        assert!(matches!(
+            warnings.as_slice(),
+            [
+                ParseWarning::SkippedLine { line: 1, .. },
+                ParseWarning::SkippedLine { line: 2, .. },
+                ParseWarning::Unparsable { .. },
+            ]
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! slash separated paths to single attributes: `TRACK[2]/VOLPAN[1]` is the second value of the
+//! first `VOLPAN` line of the third `TRACK` object. every step but the last names a child
+//! object, the last one names a line. indices start at 0 and default to it, and the path may
+//! start with the name of the root object (`REAPER_PROJECT/TEMPO`)
+use super::*;
+
+/// `(name, index)` of every step
+fn parse_path(path: &str) -> Option<Vec<(&str, usize)>> {
+    path.split('/')
+        .map(|step| match step.split_once('[') {
+            Some((name, index)) => index
+                .strip_suffix(']')
+                .and_then(|index| index.parse().ok())
+                .map(|index| (name, index)),
+            None => Some((step, 0)),
+        })
+        .map(|step| step.filter(|(name, _)| !name.is_empty()))
+        .collect()
+}
+
+impl Object {
+    fn resolve_path<'path>(
+        &self,
+        path: &'path str,
+    ) -> error::Result<(Vec<(&'path str, usize)>, usize)> {
+        let steps = parse_path(path).ok_or_else(|| error::Error::InvalidPath {
+            path: path.to_owned(),
+        })?;
+        let skip = match steps.as_slice() {
+            [(root, 0), _, ..] if *root == self.header.attribute.as_ref() => 1,
+            _ => 0,
+        };
+        Ok((steps, skip))
+    }
+
+    /// attribute `path` points to, see the [module docs](self::path)
+    pub fn attribute_at(&self, path: &str) -> error::Result<&Attribute> {
+        let (steps, skip) = self.resolve_path(path)?;
+        let not_found = || error::Error::PathNotFound {
+            path: path.to_owned(),
+        };
+        let (&(line, index), objects) = steps[skip..].split_last().ok_or_else(not_found)?;
+        objects
+            .iter()
+            .try_fold(self, |object, &(name, index)| {
+                object
+                    .values
+                    .iter()
+                    .filter_map(Entry::as_object)
+                    .filter(|child| child.header.attribute.as_ref() == name)
+                    .nth(index)
+            })
+            .and_then(|object| object.attributes(line))
+            .and_then(|values| values.get(index))
+            .ok_or_else(not_found)
+    }
+
+    /// mutable counterpart of [`Object::attribute_at`]
+    pub fn attribute_at_mut(&mut self, path: &str) -> error::Result<&mut Attribute> {
+        let (steps, skip) = self.resolve_path(path)?;
+        let not_found = || error::Error::PathNotFound {
+            path: path.to_owned(),
+        };
+        let (&(line, index), objects) = steps[skip..].split_last().ok_or_else(not_found)?;
+        objects
+            .iter()
+            .try_fold(self, |object, &(name, index)| {
+                object
+                    .values
+                    .iter_mut()
+                    .filter_map(Entry::as_object_mut)
+                    .filter(|child| child.header.attribute.as_ref() == name)
+                    .nth(index)
+            })
+            .and_then(|object| object.attributes_mut(line))
+            .and_then(|values| values.get_mut(index))
+            .ok_or_else(not_found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::Result;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("TRACK[2]/VOLPAN[1]"),
+            Some(vec![("TRACK", 2), ("VOLPAN", 1)])
+        );
+        assert_eq!(parse_path("TEMPO"), Some(vec![("TEMPO", 0)]));
+        assert_eq!(parse_path(""), None);
+        assert_eq!(parse_path("TRACK//NAME"), None);
+        assert_eq!(parse_path("TRACK[x]/NAME"), None);
+        assert_eq!(parse_path("TRACK[1/NAME"), None);
+    }
+
+    #[test]
+    fn test_attribute_at() -> Result<()> {
+        let mut project = from_str(EXAMPLE_1)?;
+        let tempo = project.attribute_at("TEMPO")?.clone();
+        assert_eq!(project.attribute_at("REAPER_PROJECT/TEMPO[0]")?, &tempo);
+        assert_eq!(
+            Some(project.attribute_at("TRACK[2]/VOLPAN[1]")?),
+            project
+                .query("REAPER_PROJECT > TRACK")
+                .get(2)
+                .and_then(|track| track.attributes("VOLPAN"))
+                .and_then(|values| values.get(1))
+        );
+        *project.attribute_at_mut("TRACK[2]/NAME")? = "\"renamed\"".parse()?;
+        assert_eq!(
+            project.attribute_at("TRACK[2]/NAME")?.as_str(),
+            Some("renamed")
+        );
+        assert!(matches!(
+            project.attribute_at("TRACK[1000]/NAME"),
+            Err(error::Error::PathNotFound { .. })
+        ));
+        assert!(matches!(
+            project.attribute_at("TEMPO[99]"),
+            Err(error::Error::PathNotFound { .. })
+        ));
+        assert!(matches!(
+            project.attribute_at("TRACK[]/NAME"),
+            Err(error::Error::InvalidPath { .. })
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! css-like selectors over object headers: `TRACK > ITEM > SOURCE` matches `SOURCE` objects
+//! directly inside an `ITEM` directly inside a `TRACK`, `TRACK SOURCE` matches `SOURCE` objects
+//! anywhere below a `TRACK`
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Child,
+    Descendant,
+}
+
+/// `(combinator joining this step to the previous one, header attribute name)`
+type Step<'selector> = (Combinator, &'selector str);
+
+/// `None` for an empty selector or a dangling `>`
+fn parse_selector(selector: &str) -> Option<Vec<Step<'_>>> {
+    let mut steps = vec![];
+    for (segment_index, segment) in selector.split('>').enumerate() {
+        let mut names = segment.split_whitespace().peekable();
+        names.peek()?;
+        steps.extend(names.enumerate().map(|(index, name)| {
+            match segment_index > 0 && index == 0 {
+                true => (Combinator::Child, name),
+                false => (Combinator::Descendant, name),
+            }
+        }));
+    }
+    Some(steps)
+}
+
+/// whether the last object of `path` (root first) matches `steps`
+fn matches(steps: &[Step], path: &[&Object]) -> bool {
+    let (Some(((combinator, name), steps)), Some((object, ancestors))) =
+        (steps.split_last(), path.split_last())
+    else {
+        return false;
+    };
+    if object.header.attribute.as_ref() != name {
+        return false;
+    }
+    match (steps.is_empty(), combinator) {
+        (true, _) => true,
+        (false, Combinator::Child) => matches(steps, ancestors),
+        (false, Combinator::Descendant) => {
+            (1..=ancestors.len()).any(|end| matches(steps, &ancestors[..end]))
+        }
+    }
+}
+
+fn collect<'object>(
+    steps: &[Step],
+    path: &mut Vec<&'object Object>,
+    found: &mut Vec<&'object Object>,
+) {
+    if matches(steps, path) {
+        found.extend(path.last());
+    }
+    let Some(object) = path.last().copied() else {
+        return;
+    };
+    for child in object.values.iter().filter_map(|entry| entry.as_object()) {
+        path.push(child);
+        collect(steps, path, found);
+        path.pop();
+    }
+}
+
+impl Object {
+    /// every object (this one included) matching `selector`, in document order.
+    /// names are separated by `>` (direct child) or whitespace (descendant), so
+    /// `REAPER_PROJECT > TRACK ITEM` matches items at any depth below top level tracks.
+    /// a malformed selector (empty, or with a dangling `>`) matches nothing
+    pub fn query(&self, selector: &str) -> Vec<&Object> {
+        let mut found = vec![];
+        if let Some(steps) = parse_selector(selector) {
+            collect(&steps, &mut vec![self], &mut found);
+        }
+        found
+    }
+
+    /// first object matching `selector`, see [`Object::query`]
+    pub fn query_one(&self, selector: &str) -> Option<&Object> {
+        self.query(selector).into_iter().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::Result;
+
+    const EXAMPLE_1: &str = include_str!("../../test_data/barbarah-anne.rpp");
+
+    #[test]
+    fn test_parse_selector() {
+        use Combinator::*;
+        assert_eq!(
+            parse_selector("TRACK>ITEM  SOURCE"),
+            Some(vec![
+                (Descendant, "TRACK"),
+                (Child, "ITEM"),
+                (Descendant, "SOURCE")
+            ])
+        );
+        assert_eq!(parse_selector(""), None);
+        assert_eq!(parse_selector("> TRACK"), None);
+        assert_eq!(parse_selector("TRACK >"), None);
+        assert_eq!(parse_selector("TRACK > > ITEM"), None);
+    }
+
+    #[test]
+    fn test_query() -> Result<()> {
+        let project = from_str(EXAMPLE_1)?;
+        let sources = project.query("TRACK > ITEM > SOURCE");
+        assert!(!sources.is_empty());
+        assert!(sources
+            .iter()
+            .all(|source| source.header.attribute.as_ref() == "SOURCE"));
+        assert_eq!(project.query("TRACK SOURCE"), sources);
+        assert_eq!(
+            project.query("REAPER_PROJECT > SOURCE"),
+            Vec::<&Object>::new()
+        );
+        assert_eq!(
+            project.query("REAPER_PROJECT > TRACK").len(),
+            EXAMPLE_1.matches("\n  <TRACK ").count()
+        );
+        assert_eq!(
+            project
+                .query_one("REAPER_PROJECT > METRONOME")
+                .and_then(|metronome| metronome.single_attribute("BEATLEN")),
+            Some(&Attribute::Int(Int(4)))
+        );
+        assert_eq!(project.query_one("TRACK > METRONOME"), None);
+        Ok(())
+    }
+}
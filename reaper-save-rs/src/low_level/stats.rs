@@ -0,0 +1,133 @@
+//! what the parser made of a file, as a sanity check. values it could not read as anything
+//! else end up as unquoted strings, so a lot of those points at a gap in the parser
+use super::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// objects, the root included
+    pub objects: usize,
+    pub lines: usize,
+    pub anonymous_parameters: usize,
+    /// values of lines and object headers by kind
+    pub attributes: HashMap<AttributeKind, usize>,
+    /// the part of the [`AttributeKind::String`] values written without quotes
+    pub unquoted_strings: usize,
+}
+
+impl ParseStats {
+    fn count_attribute(&mut self, attribute: &Attribute) {
+        *self.attributes.entry(attribute.kind()).or_default() += 1;
+        if let Attribute::String(ReaperString::Unquoted(_)) = attribute {
+            self.unquoted_strings += 1;
+        }
+    }
+
+    fn count_object(&mut self, object: &Object) {
+        self.objects += 1;
+        object
+            .header
+            .values
+            .iter()
+            .for_each(|value| self.count_attribute(value));
+        for entry in &object.values {
+            match entry {
+                Entry::Object(object) => self.count_object(object),
+                Entry::Line(line) => {
+                    self.lines += 1;
+                    line.values
+                        .iter()
+                        .for_each(|value| self.count_attribute(value));
+                }
+                Entry::AnonymousParameter(_) => self.anonymous_parameters += 1,
+                Entry::Blank => {}
+            }
+        }
+    }
+}
+
+impl Object {
+    /// counts of everything in this object, nested objects included
+    pub fn parse_stats(&self) -> ParseStats {
+        ParseStats::default().tap_mut(|stats| stats.count_object(self))
+    }
+
+    /// objects, lines and anonymous parameters in this object, itself and nested ones included
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .values
+            .iter()
+            .map(|entry| match entry {
+                Entry::Object(object) => object.node_count(),
+                Entry::Line(_) | Entry::AnonymousParameter(_) => 1,
+                Entry::Blank => 0,
+            })
+            .sum::<usize>()
+    }
+
+    /// deepest nesting of objects below this one, `0` if it has no child objects. counted the
+    /// same way as [`ParseOptions::max_depth`]
+    pub fn max_depth(&self) -> usize {
+        self.values
+            .iter()
+            .filter_map(|entry| entry.as_object())
+            .map(|object| object.max_depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::Result;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_stats() -> Result<()> {
+        let object = from_str(
+            "<TRACK {C7D7917F-D94F-ED85-1D58-2F258596E414}\r\n  NAME \"a\" b 1 0.5 -1:U\r\n  <VST c\r\n    ZXZhdxgAAQ==\r\n  >\r\n>",
+        )?;
+        assert_eq!(
+            object.parse_stats(),
+            ParseStats {
+                objects: 2,
+                lines: 1,
+                anonymous_parameters: 1,
+                attributes: HashMap::from([
+                    (AttributeKind::ReaperUid, 1),
+                    (AttributeKind::String, 3),
+                    (AttributeKind::Int, 1),
+                    (AttributeKind::Float, 1),
+                    (AttributeKind::UNumber, 1),
+                ]),
+                unquoted_strings: 2,
+            }
+        );
+        assert_eq!(object.node_count(), 4);
+        assert_eq!(object.max_depth(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_depth_matches_parse_options() -> Result<()> {
+        let nested = "<A
+  <B
+    <C
+    >
+  >
+  <D
+  >
+>";
+        let object = from_str(nested)?;
+        assert_eq!(object.max_depth(), 2);
+        assert_eq!(object.node_count(), 4);
+        let options = |max_depth| ParseOptions {
+            max_depth,
+            ..Default::default()
+        };
+        assert!(from_str_with(nested, &options(2)).is_ok());
+        assert!(from_str_with(nested, &options(1)).is_err());
+        Ok(())
+    }
+}
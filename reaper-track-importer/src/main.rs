@@ -1,4 +1,4 @@
-use std::{ops::Not, path::PathBuf, str::FromStr};
+use std::{ops::Not, path::PathBuf};
 
 use eyre::{Context, ContextCompat, Result};
 use reaper_save_rs::high_level::{ReaperProject, Track};
@@ -48,14 +48,15 @@ struct TrackSelection {
 
 impl std::fmt::Display for TrackSelection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}, ({} items)",
-            self.track
-                .name()
-                .expect("this is a bug, please report it to [wojciech.brozek@niedzwiedz.it]"),
-            self.track.items().len(),
-        )
+        match self.track.info() {
+            Ok(info) => write!(
+                f,
+                "{}, ({} items)",
+                info.name.as_deref().unwrap_or("(unnamed)"),
+                info.item_count
+            ),
+            Err(_) => write!(f, "(unreadable track)"),
+        }
     }
 }
 
@@ -101,49 +102,26 @@ fn main() -> Result<()> {
                             .then_some(v)
                             .context("no tracks selected")
                     })
-                    .map(|tracks| tracks.into_iter().map(|t| t.track).collect::<Vec<_>>())
+                    .map(|tracks| {
+                        tracks
+                            .into_iter()
+                            .map(|t| t.track.duplicate())
+                            .collect::<Vec<_>>()
+                    })
                     .and_then(|mut copied_tracks| {
-                        copied_tracks
-                            .iter_mut()
-                            .flat_map(|track| {
-                                track.modify_items(|item| {
-                                    item.with_source_waves_mut(|source| match source.file_mut() {
-                                        Some(source) => {
-                                            source.context("invalid file").and_then(|file| {
-                                                PathBuf::from_str(file.as_str())
-                                                    .context("invalid path")
-                                                    .and_then(|item_path| {
-                                                        match item_path.is_absolute() {
-                                                            true => Ok(file.clone()),
-                                                            false => source_path
-                                                                .parent()
-                                                                .context(
-                                                                    "source path has no parent",
-                                                                )
-                                                                .map(|parent| {
-                                                                    parent
-                                                                        .join(item_path)
-                                                                        .display()
-                                                                        .to_string()
-                                                                }),
-                                                        }
-                                                    })
-                                                    .map(|corrected| {
-                                                        info!(
-                                                            "correcting path [{file}] -> \
-                                                             [{corrected}]"
-                                                        );
-                                                        *file = corrected;
-                                                    })
-                                            })
-                                        }
-                                        None => Ok(()),
-                                    })
-                                })
-                            })
-                            .flatten()
-                            .collect::<Result<()>>()
-                            .map(|_| copied_tracks)
+                        zip_results!(
+                            Error = eyre::Report,
+                            source_path.parent().context("source path has no parent"),
+                            target_path.parent().context("target path has no parent")
+                        )
+                        .and_then(|(source_dir, target_dir)| {
+                            info!(?source_dir, ?target_dir, "rebasing source paths");
+                            copied_tracks
+                                .iter_mut()
+                                .try_for_each(|track| track.rebase_paths(source_dir, target_dir))
+                                .context("rebasing source paths")
+                        })
+                        .map(|_| copied_tracks)
                     })
                     .and_then(|copied_tracks| {
                         target_project